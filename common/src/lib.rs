@@ -1 +1,2 @@
-pub mod msg;
\ No newline at end of file
+pub mod msg;
+pub mod validate;
@@ -1,7 +1,128 @@
-use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{BankMsg, Binary, Coin, CosmosMsg, Decimal, Uint128, WasmMsg};
 
 #[cw_serde]
 pub struct ProposeMemberData {
     pub owner_addr: String,
     pub proxy_addr: String,
 }
+
+#[cw_serde]
+pub enum PausableAction {
+    Donate,
+    Withdraw,
+    Propose,
+    Decay, // forced weight update
+}
+
+// membership keeps the source of truth and pushes it to every proxy
+#[cw_serde]
+#[derive(Default)]
+pub struct PauseState {
+    pub all: bool,
+    pub actions: Vec<PausableAction>,
+}
+
+impl PauseState {
+    pub fn is_paused(&self, action: &PausableAction) -> bool {
+        self.all || self.actions.contains(action)
+    }
+}
+
+// what a group treasury can spend on, membership rejects execs on the group's own contracts
+#[cw_serde]
+pub enum SpendMsg {
+    BankSend {
+        to_address: String,
+        amount: Vec<Coin>,
+    },
+    WasmExecute {
+        contract_addr: String,
+        msg: Binary,
+        funds: Vec<Coin>,
+    },
+}
+
+impl From<SpendMsg> for CosmosMsg {
+    fn from(msg: SpendMsg) -> Self {
+        match msg {
+            SpendMsg::BankSend { to_address, amount } => {
+                BankMsg::Send { to_address, amount }.into()
+            }
+            SpendMsg::WasmExecute {
+                contract_addr,
+                msg,
+                funds,
+            } => WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }
+            .into(),
+        }
+    }
+}
+
+// voucher of a token from another chain, accepted for donations next to the group denom
+#[cw_serde]
+pub struct IbcDenom {
+    pub denom: String,  // ibc/... denom on this chain
+    pub origin: String, // chain the token comes from, recorded with donations
+}
+
+// DEX router donations in other tokens are swapped through to the group denom
+#[cw_serde]
+pub struct SwapConfig {
+    pub router: String,      // Astroport-style, taking ExecuteSwapOperations
+    pub max_spread: Decimal, // slippage limit, the swap fails above it
+}
+
+// response data of proxy Donate, amounts are in the donated denom
+#[cw_serde]
+pub struct DonateData {
+    pub direct: Uint128, // creator's part, kept by the proxy or forwarded
+    pub treasury: Uint128,
+    pub distributed: Uint128, // to the distribution contracts of all groups
+    pub donations: u64,       // counted since the last weight reset
+}
+
+// response data of proxy Withdraw
+#[cw_serde]
+pub struct WithdrawData {
+    pub amount: Uint128,
+    pub denom: String,            // the group denom when everything was withdrawn
+    pub receiver: Option<String>, // None => owner, or collaborators if the revenue split is set
+    pub queued: Option<u64>,      // set when the amount waits for the timelock
+    pub old_weight: u64,
+    pub new_weight: u64,
+}
+
+// response data of proxy UpdateWeight
+#[cw_serde]
+pub struct UpdateWeightData {
+    pub performed: bool, // false until halftime passed since the last update
+    pub old_weight: u64,
+    pub new_weight: u64,
+}
+
+// membership instantiates the distribution contract with it
+#[cw_serde]
+pub struct DistributionInstantiateMsg {
+    pub denom: String,
+    pub ibc_denoms: Vec<String>, // distributed and paid out the same way as denom
+    pub membership_contract: String,
+}
+
+// what membership expects the distribution contract to answer
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum DistributionQueryMsg {
+    // share of the proxy not withdrawn yet, in the group denom
+    #[returns(PendingRewardsResponse)]
+    PendingRewards { proxy: String },
+}
+
+#[cw_serde]
+pub struct PendingRewardsResponse {
+    pub amount: Uint128,
+}
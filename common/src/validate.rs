@@ -0,0 +1,69 @@
+use cosmwasm_std::Decimal;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ValidationError {
+    #[error("Invalid denom: {0:?}")]
+    InvalidDenom(String),
+    #[error("Direct part has to be between 0 and 1")]
+    InvalidDirectPart,
+    #[error("Halftime has to be positive")]
+    ZeroHalftime,
+    #[error("Starting weight has to be positive")]
+    ZeroStartingWeight,
+    #[error("Initial member {0} is listed more than once")]
+    DuplicatedInitialMember(String),
+}
+
+// instantiate msgs of both contracts, checked before anything is stored or spawned
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+// cosmos-sdk rules: a letter followed by 2 to 127 letters, digits or one of /:._-
+pub fn denom(denom: &str) -> Result<(), ValidationError> {
+    let mut chars = denom.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && (3..=128).contains(&denom.len())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidDenom(denom.to_owned()))
+    }
+}
+
+pub fn direct_part(direct_part: Decimal) -> Result<(), ValidationError> {
+    if direct_part > Decimal::one() {
+        return Err(ValidationError::InvalidDirectPart);
+    }
+    Ok(())
+}
+
+pub fn halftime(halftime: u64) -> Result<(), ValidationError> {
+    if halftime == 0 {
+        return Err(ValidationError::ZeroHalftime);
+    }
+    Ok(())
+}
+
+pub fn starting_weight(weight: u64) -> Result<(), ValidationError> {
+    if weight == 0 {
+        return Err(ValidationError::ZeroStartingWeight);
+    }
+    Ok(())
+}
+
+// bech32 addresses are case insensitive, "ORAI1..." and "orai1..." are the same account
+pub fn unique_members(members: &[String]) -> Result<(), ValidationError> {
+    let normalised: Vec<_> = members.iter().map(|member| member.to_lowercase()).collect();
+    for (idx, member) in normalised.iter().enumerate() {
+        if normalised[..idx].contains(member) {
+            return Err(ValidationError::DuplicatedInitialMember(
+                members[idx].clone(),
+            ));
+        }
+    }
+    Ok(())
+}
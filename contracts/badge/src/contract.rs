@@ -0,0 +1,64 @@
+use cosmwasm_std::{ensure, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+
+use crate::error::ContractError;
+use crate::msg::{ExecMsg, InstantiateMsg, QueryMsg};
+use crate::state::{Badges, TRANSFERABLE};
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    TRANSFERABLE.save(deps.storage, &msg.transferable)?;
+
+    let msg = cw721_base::InstantiateMsg {
+        name: msg.name,
+        symbol: msg.symbol,
+        minter: msg.minter,
+    };
+    let resp = Badges::default().instantiate(deps, env, info, msg)?;
+    Ok(resp)
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecMsg,
+) -> Result<Response, ContractError> {
+    let badges = Badges::default();
+
+    match &msg {
+        ExecMsg::TransferNft { .. } | ExecMsg::SendNft { .. } => {
+            ensure!(
+                TRANSFERABLE.load(deps.storage)?,
+                ContractError::NotTransferable
+            );
+        }
+        // minter burns badges of members who left, base contract allows it only to the owner
+        ExecMsg::Burn { token_id } if is_minter(deps.as_ref(), &badges, &info)? => {
+            badges.tokens.remove(deps.storage, token_id)?;
+            badges.decrement_tokens(deps.storage)?;
+
+            let resp = Response::new()
+                .add_attribute("action", "burn")
+                .add_attribute("sender", info.sender.as_str())
+                .add_attribute("token_id", token_id.as_str());
+            return Ok(resp);
+        }
+        _ => {}
+    }
+
+    let resp = badges.execute(deps, env, info, msg)?;
+    Ok(resp)
+}
+
+fn is_minter(deps: Deps, badges: &Badges, info: &MessageInfo) -> StdResult<bool> {
+    let minter = badges.minter(deps)?.minter;
+    Ok(minter.as_deref() == Some(info.sender.as_str()))
+}
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    Badges::default().query(deps, env, msg)
+}
@@ -0,0 +1,12 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    #[error("{0}")]
+    Cw721(#[from] cw721_base::ContractError),
+    #[error("Badges are not transferable")]
+    NotTransferable,
+}
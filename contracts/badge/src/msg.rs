@@ -0,0 +1,20 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Empty;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub minter: String, // membership contract
+    pub transferable: bool,
+}
+
+// carried by every badge, token id is the proxy address as well
+#[cw_serde]
+pub struct BadgeMetadata {
+    pub proxy: String,
+    pub joined_at: u64,
+}
+
+pub type ExecMsg = cw721_base::ExecuteMsg<BadgeMetadata, Empty>;
+pub type QueryMsg = cw721_base::QueryMsg<Empty>;
@@ -0,0 +1,84 @@
+use anyhow::Result as AnyResult;
+use cosmwasm_std::Addr;
+use cw721::{NftInfoResponse, NumTokensResponse, OwnerOfResponse};
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+use crate::{
+    execute, instantiate,
+    msg::{BadgeMetadata, ExecMsg, QueryMsg},
+    query,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct CodeId(u64);
+
+impl CodeId {
+    pub fn store_code(app: &mut App) -> Self {
+        let contract = ContractWrapper::new(execute, instantiate, query);
+        CodeId(app.store_code(Box::new(contract)))
+    }
+}
+
+impl From<CodeId> for u64 {
+    fn from(value: CodeId) -> Self {
+        value.0
+    }
+}
+
+#[derive(Debug)]
+pub struct Contract(Addr);
+
+impl Contract {
+    pub fn addr(&self) -> &Addr {
+        &self.0
+    }
+
+    pub fn from_addr(addr: Addr) -> Self {
+        Self(addr)
+    }
+
+    #[track_caller]
+    pub fn transfer(
+        &self,
+        app: &mut App,
+        sender: &str,
+        recipient: &str,
+        token_id: &str,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::TransferNft {
+            recipient: recipient.to_owned(),
+            token_id: token_id.to_owned(),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    pub fn owner_of(&self, app: &App, token_id: &str) -> AnyResult<OwnerOfResponse> {
+        let resp = app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::OwnerOf {
+                token_id: token_id.to_owned(),
+                include_expired: None,
+            },
+        )?;
+        Ok(resp)
+    }
+
+    pub fn nft_info(&self, app: &App, token_id: &str) -> AnyResult<NftInfoResponse<BadgeMetadata>> {
+        let resp = app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::NftInfo {
+                token_id: token_id.to_owned(),
+            },
+        )?;
+        Ok(resp)
+    }
+
+    pub fn num_tokens(&self, app: &App) -> AnyResult<u64> {
+        let resp: NumTokensResponse = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::NumTokens {})?;
+        Ok(resp.count)
+    }
+}
@@ -0,0 +1,9 @@
+use cosmwasm_std::Empty;
+use cw721_base::Cw721Contract;
+use cw_storage_plus::Item;
+
+use crate::msg::BadgeMetadata;
+
+pub type Badges<'a> = Cw721Contract<'a, BadgeMetadata, Empty, Empty, Empty>;
+
+pub const TRANSFERABLE: Item<bool> = Item::new("transferable"); // false => soulbound badges
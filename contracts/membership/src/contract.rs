@@ -1,30 +1,58 @@
 use std::vec;
 
 use cosmwasm_std::{
-    ensure, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response,
-    StdResult, SubMsg, WasmMsg,
+    ensure, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply,
+    Response, StdResult, SubMsg, WasmMsg,
 };
 
 mod exec;
 mod query;
 mod reply;
 
+use common::validate::Validate;
+use exec::proxy_receipts;
+
 use crate::error::ContractError;
-use crate::msg::{ExecMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Config, AWAITING_INITIAL_RESPS, CONFIG};
+use crate::msg::{ExecMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{
+    Config, AWAITING_INITIAL_RESPS, CONFIG, HOOKS, INITIAL_MEMBERS, MEMBER_COUNT, TOTAL_WEIGHT,
+};
 
 // Get instantiate msg of proxy contract
+use badge::msg::InstantiateMsg as BadgeInstantiateMsg;
+use common::msg::DistributionInstantiateMsg;
 use proxy::msg::InstantiateMsg as ProxyInstantiateMsg;
+use receipt::msg::InstantiateMsg as ReceiptInstantiateMsg;
 
 const INITIAL_PROXY_INSTANTIATION_REPLY_ID: u64 = 1;
 const PROXY_INSTANTIATION_REPLY_ID: u64 = 2;
+const DISTRIBUTION_INSTANTIATION_REPLY_ID: u64 = 3;
+const BADGE_INSTANTIATION_REPLY_ID: u64 = 4;
+const RECEIPT_INSTANTIATION_REPLY_ID: u64 = 5;
+const CASCADE_REPLY_ID: u64 = 6;
+
+const DEFAULT_SPEND_VOTING_PERIOD: u64 = 3600 * 24 * 7;
 
 pub fn instantiate(
     deps: DepsMut,
     env: Env,
-    info: MessageInfo,
+    _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    // validate addresses, proxies are created once the distribution contract exists
+    // duplicates are checked on normalised addresses so one member can't take two places
+    let mut initial_members: Vec<Addr> = vec![];
+    for member in &msg.initial_members {
+        let member = deps.api.addr_validate(member)?;
+        ensure!(
+            !initial_members.contains(&member),
+            ContractError::DuplicateMember(member.into_string())
+        );
+        initial_members.push(member);
+    }
+
+    msg.validate()?;
+
     ensure!(
         msg.minimal_acceptance >= 2,
         ContractError::NotEnoughRequiredAcceptances
@@ -35,41 +63,170 @@ pub fn instantiate(
         ContractError::NotEnoughInitialMembers
     );
 
+    ensure!(
+        msg.max_members
+            .is_none_or(|max| msg.initial_members.len() as u64 <= max),
+        ContractError::GroupFull
+    );
+
+    let treasury_part = msg.treasury_part.unwrap_or_default();
+    ensure!(
+        treasury_part <= Decimal::percent(100),
+        ContractError::InvalidTreasuryPart
+    );
+    ensure!(
+        msg.swap
+            .as_ref()
+            .is_none_or(|swap| swap.max_spread <= Decimal::percent(100)),
+        ContractError::InvalidMaxSpread
+    );
+
+    let spend_voting_period = msg
+        .spend_voting_period
+        .unwrap_or(DEFAULT_SPEND_VOTING_PERIOD);
+    ensure!(
+        spend_voting_period > 0,
+        ContractError::InvalidSpendVotingPeriod
+    );
+
+    let guardian = msg
+        .guardian
+        .map(|guardian| deps.api.addr_validate(&guardian))
+        .transpose()?;
+
     let config = Config {
         starting_weight: msg.starting_weight,
         denom: msg.denom.clone(),
         direct_part: msg.direct_part,
         halftime: msg.halftime,
         proxy_code_id: msg.proxy_code_id,
-        distribution_contract: Addr::unchecked(""), // cause we don't have distribution contract yet
+        distribution_contract: Addr::unchecked(""), // set when the distribution contract is instantiated
         minimal_acceptances: msg.minimal_acceptance,
+        guardian,
+        max_members: msg.max_members,
+        receipt_threshold: msg.receipts.as_ref().map(|receipts| receipts.threshold),
+        treasury_part,
+        ibc_denoms: msg.ibc_denoms.unwrap_or_default(),
+        swap: msg.swap,
+        spend_voting_period,
     };
 
     CONFIG.save(deps.storage, &config)?;
+    MEMBER_COUNT.save(deps.storage, &0)?;
+    TOTAL_WEIGHT.save(deps.storage, &0, env.block.height)?;
+
+    INITIAL_MEMBERS.save(deps.storage, &initial_members)?;
+
+    // proxies send donations and weight changes, including the zeroed weight of a removed
+    // member, to the distribution address they got on instantiation; an empty one would make
+    // all of those fail, so the distribution contract is created before any proxy
+    let distribution_init_msg = DistributionInstantiateMsg {
+        denom: msg.denom,
+        ibc_denoms: config
+            .ibc_denoms
+            .iter()
+            .map(|ibc| ibc.denom.clone())
+            .collect(),
+        membership_contract: env.contract.address.to_string(),
+    };
+    let distribution_init_msg = WasmMsg::Instantiate {
+        admin: Some(env.contract.address.to_string()),
+        code_id: msg.distribution_code_id,
+        msg: to_json_binary(&distribution_init_msg)?,
+        funds: vec![],
+        label: "Distribution".to_owned(),
+    };
+    let distribution_init_msg =
+        SubMsg::reply_on_success(distribution_init_msg, DISTRIBUTION_INSTANTIATION_REPLY_ID);
+
+    // badge contract has to exist before the first proxy is registered
+    let badge_init_msg = msg
+        .badge
+        .map(|badge| -> StdResult<_> {
+            let badge_init_msg = BadgeInstantiateMsg {
+                name: "Membership badge".to_owned(),
+                symbol: "BADGE".to_owned(),
+                minter: env.contract.address.to_string(),
+                transferable: badge.transferable,
+            };
+            let badge_init_msg = WasmMsg::Instantiate {
+                admin: Some(env.contract.address.to_string()),
+                code_id: badge.code_id,
+                msg: to_json_binary(&badge_init_msg)?,
+                funds: vec![],
+                label: "Membership badge".to_owned(),
+            };
+            Ok(SubMsg::reply_on_success(
+                badge_init_msg,
+                BADGE_INSTANTIATION_REPLY_ID,
+            ))
+        })
+        .transpose()?;
+
+    // proxies get the receipt contract address on instantiation
+    let receipt_init_msg = msg
+        .receipts
+        .map(|receipts| -> StdResult<_> {
+            let receipt_init_msg = ReceiptInstantiateMsg {
+                name: "Donation receipt".to_owned(),
+                symbol: "RECEIPT".to_owned(),
+                membership_contract: env.contract.address.to_string(),
+            };
+            let receipt_init_msg = WasmMsg::Instantiate {
+                admin: Some(env.contract.address.to_string()),
+                code_id: receipts.code_id,
+                msg: to_json_binary(&receipt_init_msg)?,
+                funds: vec![],
+                label: "Donation receipt".to_owned(),
+            };
+            Ok(SubMsg::reply_on_success(
+                receipt_init_msg,
+                RECEIPT_INSTANTIATION_REPLY_ID,
+            ))
+        })
+        .transpose()?;
+
+    let resp = Response::new()
+        .add_submessages(badge_init_msg)
+        .add_submessages(receipt_init_msg)
+        .add_submessage(distribution_init_msg);
+    // reply handler of distribution instantiation creates proxies for initial members
+
+    Ok(resp)
+}
+
+// proxies have to know the distribution contract, so they are created after it
+pub(crate) fn initial_proxy_instantiate_msgs(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    initial_members: Vec<Addr>,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let receipts = proxy_receipts(deps.storage, config)?;
 
-    let proxy_instantiate_msgs: Vec<_> = msg
-        .initial_members
+    let proxy_instantiate_msgs: Vec<_> = initial_members
         .into_iter()
-        .map(|member| -> Result<_, ContractError> {
+        .map(|addr| -> Result<_, ContractError> {
             // can use ? operator here because return Result<Response, ContractError>
 
-            // validate address
-            let addr = deps.api.addr_validate(&member)?;
-
             let proxy_init_msg = ProxyInstantiateMsg {
                 owner: addr.to_string(),
-                weight: msg.starting_weight,
-                denom: msg.denom.clone(),
-                direct_part: msg.direct_part,
-                distribution_contract: "".to_owned(), // cause we don't have distribution contract yet
+                weight: config.starting_weight,
+                denom: config.denom.clone(),
+                direct_part: config.direct_part,
+                distribution_contract: config.distribution_contract.to_string(),
                 membership_contract: env.contract.address.to_string(),
-                halftime: msg.halftime,
+                halftime: config.halftime,
+                receipts: receipts.clone(),
+                treasury_part: Some(config.treasury_part),
+                ibc_denoms: Some(config.ibc_denoms.clone()),
+                swap: config.swap.clone(),
             };
 
             // blockchain will instantiate proxy contract with below information
             let msg = WasmMsg::Instantiate {
                 admin: Some(env.contract.address.to_string()),
-                code_id: msg.proxy_code_id,
+                code_id: config.proxy_code_id,
                 // this one will go to entry point of proxy contract
                 msg: to_json_binary(&proxy_init_msg)?, // to_binary deprecated
                 funds: vec![],
@@ -85,11 +242,9 @@ pub fn instantiate(
 
     AWAITING_INITIAL_RESPS.save(deps.storage, &(proxy_instantiate_msgs.len() as _))?;
 
-    let resp = Response::new().add_submessages(proxy_instantiate_msgs);
     // these submessages provide reply to reply entry point of membership contract with INITIAL_PROXY_INSTANTIATION_REPLY_ID
     // and membership contract will have reply handler for those replies
-
-    Ok(resp)
+    Ok(proxy_instantiate_msgs)
 }
 
 pub fn execute(
@@ -100,15 +255,54 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecMsg::ProposeMember { addr } => exec::propose_member(deps, env, info, addr),
+        ExecMsg::OwnershipTransferred {
+            previous_owner,
+            new_owner,
+        } => exec::ownership_transferred(deps, env, info, previous_owner, new_owner),
+        ExecMsg::Pause { actions } => exec::pause(deps, info, actions),
+        ExecMsg::ProposeUnpause {} => exec::propose_unpause(deps, info),
+        ExecMsg::ProposeRemoval { member, reason } => {
+            exec::propose_removal(deps, env, info, member, reason)
+        }
+        ExecMsg::WeightChanged { weight } => exec::weight_changed(deps, env, info, weight),
+        ExecMsg::Donated { amount } => exec::donated(deps, info, amount),
+        ExecMsg::Decayed {} => exec::decayed(deps, env, info),
+        ExecMsg::AddHook { addr } => exec::add_hook(deps, info, addr),
+        ExecMsg::RemoveHook { addr } => exec::remove_hook(deps, info, addr),
+        ExecMsg::ProposeSpend { msgs, description } => {
+            exec::propose_spend(deps, env, info, msgs, description)
+        }
+        ExecMsg::VoteSpend { id } => exec::vote_spend(deps, env, info, id),
+        ExecMsg::JoinParent { parent } => exec::join_parent(deps, info, parent),
+        ExecMsg::ParentAccepted {
+            distribution_contract,
+        } => exec::parent_accepted(deps, env, info, distribution_contract),
+        ExecMsg::ParentRemoved {} => exec::parent_removed(deps, env, info),
+        ExecMsg::Leave {} => exec::leave(deps, env, info),
+        ExecMsg::Cascade {} => exec::cascade(deps, env),
     }
 }
 
+pub fn migrate(_deps: DepsMut, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(Response::new().add_attribute("action", "migrate"))
+}
+
 pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, ContractError> {
     match reply.id {
+        DISTRIBUTION_INSTANTIATION_REPLY_ID => {
+            reply::distribution_instantiated(deps, env, reply.result.into_result())
+        }
+        BADGE_INSTANTIATION_REPLY_ID => reply::badge_instantiated(deps, reply.result.into_result()),
+        RECEIPT_INSTANTIATION_REPLY_ID => {
+            reply::receipt_instantiated(deps, reply.result.into_result())
+        }
         INITIAL_PROXY_INSTANTIATION_REPLY_ID => {
-            reply::initial_proxy_instantiated(deps, reply.result.into_result())
+            reply::initial_proxy_instantiated(deps, env, reply.result.into_result())
         }
-        PROXY_INSTANTIATION_REPLY_ID => reply::proxy_instantiated(deps, reply.result.into_result()),
+        PROXY_INSTANTIATION_REPLY_ID => {
+            reply::proxy_instantiated(deps, env, reply.result.into_result())
+        }
+        CASCADE_REPLY_ID => reply::cascaded(deps, env),
         id => Err(ContractError::UnrecognizedReplyId(id)),
     }
 }
@@ -116,5 +310,24 @@ pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, Contract
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::IsMember { addr } => to_json_binary(&query::is_member(deps, addr)?),
+        QueryMsg::Paused {} => to_json_binary(&query::paused(deps)?),
+        QueryMsg::GroupInfo {} => to_json_binary(&query::group_info(deps)?),
+        QueryMsg::Waitlist {} => to_json_binary(&query::waitlist(deps)?),
+        QueryMsg::Member { addr, at_height } => {
+            to_json_binary(&query::member(deps, addr, at_height)?)
+        }
+        QueryMsg::ListMembers { start_after, limit } => {
+            to_json_binary(&query::list_members(deps, start_after, limit)?)
+        }
+        QueryMsg::TotalWeight { at_height } => {
+            to_json_binary(&query::total_weight(deps, at_height)?)
+        }
+        QueryMsg::Hooks {} => to_json_binary(&HOOKS.query_hooks(deps)?),
+        QueryMsg::SpendProposal { id } => to_json_binary(&query::spend_proposal(deps, env, id)?),
+        QueryMsg::ListSpendProposals { start_after, limit } => {
+            to_json_binary(&query::list_spend_proposals(deps, env, start_after, limit)?)
+        }
+        QueryMsg::GroupStats {} => to_json_binary(&query::group_stats(deps, env)?),
+        QueryMsg::MemberDetails { owner } => to_json_binary(&query::member_details(deps, owner)?),
     }
 }
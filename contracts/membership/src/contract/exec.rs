@@ -1,15 +1,391 @@
+use common::msg::{PausableAction, PauseState, ProposeMemberData, SpendMsg};
 use cosmwasm_std::{
-    ensure, to_json_binary, Addr, DepsMut, Empty, Env, MessageInfo, Order, Response, SubMsg,
-    WasmMsg,
+    ensure, to_json_binary, Addr, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    QuerierWrapper, Response, StdResult, Storage, SubMsg, WasmMsg,
 };
 
 use crate::{
-    contract::PROXY_INSTANTIATION_REPLY_ID,
+    contract::{CASCADE_REPLY_ID, PROXY_INSTANTIATION_REPLY_ID},
     error::ContractError,
-    state::{CONFIG, MEMBERS, PROPOSALS, VOTES},
+    msg::{ExecMsg, GroupInfoResponse, QueryMsg},
+    state::{
+        Config, Parent, SpendProposal, SpendStatus, BADGE_CONTRACT, CASCADE_BALANCE, CONFIG,
+        FEDERATED_PROXIES, HOOKS, LAST_DECAY, MEMBERS, MEMBER_COUNT, NEXT_SPEND_ID, PARENT, PAUSED,
+        PENDING_ADDITIONS, PENDING_REMOVALS, PROPOSALS, PROXIES, RECEIPT_CONTRACT, SPEND_PROPOSALS,
+        SPEND_VOTES, SUBGROUPS, TOTAL_DONATED, TOTAL_WEIGHT, UNPAUSE_VOTES, VOTES, WAITLIST,
+        WEIGHTS,
+    },
 };
 
-use proxy::msg::InstantiateMsg as ProxyInstantiateMsg;
+use badge::msg::{BadgeMetadata, ExecMsg as BadgeExecMsg};
+use cw4::{MemberChangedHookMsg, MemberDiff, TotalWeightResponse};
+use cw_storage_plus::Item;
+use proxy::msg::{
+    DistribtionExecMsg, ExecMsg as ProxyExecMsg, InstantiateMsg as ProxyInstantiateMsg,
+    ReceiptsConfig as ProxyReceiptsConfig,
+};
+
+// membership doesn't do much itself, so only proposing is checked here, proxies check the rest
+fn ensure_not_paused(storage: &dyn Storage, action: PausableAction) -> Result<(), ContractError> {
+    let paused = PAUSED.may_load(storage)?.unwrap_or_default();
+    ensure!(!paused.is_paused(&action), ContractError::Paused);
+    Ok(())
+}
+
+pub fn set_paused_msg(proxy: &Addr, paused: &PauseState) -> StdResult<WasmMsg> {
+    let msg = ProxyExecMsg::SetPaused {
+        paused: paused.clone(),
+    };
+    let msg = WasmMsg::Execute {
+        contract_addr: proxy.to_string(),
+        msg: to_json_binary(&msg)?,
+        funds: vec![],
+    };
+    Ok(msg)
+}
+
+// proposal opens with its first vote, `pending` is PENDING_ADDITIONS or PENDING_REMOVALS
+fn open_proposal(storage: &mut dyn Storage, pending: &Item<u64>) -> StdResult<()> {
+    let count = pending.may_load(storage)?.unwrap_or_default();
+    pending.save(storage, &(count + 1))
+}
+
+// proposal is decided, votes on it must not count for the next proposal about the same address
+fn clear_proposal(storage: &mut dyn Storage, addr: &Addr, pending: &Item<u64>) -> StdResult<()> {
+    if PROPOSALS.has(storage, addr) {
+        pending.update(storage, |count| -> StdResult<_> { Ok(count - 1) })?;
+    }
+    PROPOSALS.remove(storage, addr);
+
+    let voters = MEMBERS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for voter in voters {
+        VOTES.remove(storage, (&voter, addr));
+    }
+
+    Ok(())
+}
+
+// proxy is created in reply of PROXY_INSTANTIATION_REPLY_ID
+fn proxy_instantiate_msg(
+    storage: &dyn Storage,
+    env: &Env,
+    config: &Config,
+    addr: &Addr,
+) -> StdResult<SubMsg> {
+    let proxy_init_msg = ProxyInstantiateMsg {
+        owner: addr.to_string(),
+        weight: config.starting_weight,
+        denom: config.denom.clone(),
+        direct_part: config.direct_part,
+        distribution_contract: config.distribution_contract.to_string(),
+        membership_contract: env.contract.address.to_string(),
+        halftime: config.halftime,
+        receipts: proxy_receipts(storage, config)?,
+        treasury_part: Some(config.treasury_part),
+        ibc_denoms: Some(config.ibc_denoms.clone()),
+        swap: config.swap.clone(),
+    };
+
+    let proxy_init_msg = WasmMsg::Instantiate {
+        admin: Some(env.contract.address.to_string()),
+        code_id: config.proxy_code_id,
+        msg: to_json_binary(&proxy_init_msg)?,
+        funds: vec![],
+        label: format!("{} proxy", addr),
+    };
+
+    Ok(SubMsg::reply_on_success(
+        proxy_init_msg,
+        PROXY_INSTANTIATION_REPLY_ID,
+    ))
+}
+
+// pulls the share earned in the parent group, reply of CASCADE_REPLY_ID passes it down
+fn pull_msg(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    distribution: &Addr,
+    weight: u64,
+    diff: i64,
+) -> StdResult<SubMsg> {
+    let config = CONFIG.load(storage)?;
+    let balance = querier.query_balance(&env.contract.address, &config.denom)?;
+    CASCADE_BALANCE.save(storage, &balance.amount)?;
+
+    let pull_msg = DistribtionExecMsg::Withdraw { weight, diff };
+    let pull_msg = WasmMsg::Execute {
+        contract_addr: distribution.to_string(),
+        msg: to_json_binary(&pull_msg)?,
+        funds: vec![],
+    };
+
+    Ok(SubMsg::reply_on_success(pull_msg, CASCADE_REPLY_ID))
+}
+
+// subgroup reports its total weight up like proxies do, pulling what it earned with the old one
+pub fn parent_msgs(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    diffs: &[MemberDiff],
+) -> StdResult<Vec<SubMsg>> {
+    let Some(Parent {
+        membership,
+        distribution: Some(distribution),
+    }) = PARENT.may_load(storage)?
+    else {
+        return Ok(vec![]);
+    };
+
+    let diff: i64 = diffs
+        .iter()
+        .map(|diff| diff.new.unwrap_or_default() as i64 - diff.old.unwrap_or_default() as i64)
+        .sum();
+    if diff == 0 {
+        return Ok(vec![]);
+    }
+
+    let total = TOTAL_WEIGHT.load(storage)?;
+    let weight_msg = ExecMsg::WeightChanged { weight: total };
+    let weight_msg = WasmMsg::Execute {
+        contract_addr: membership.into_string(),
+        msg: to_json_binary(&weight_msg)?,
+        funds: vec![],
+    };
+    let pull_msg = pull_msg(
+        storage,
+        querier,
+        env,
+        &distribution,
+        (total as i64 - diff) as u64,
+        diff,
+    )?;
+
+    Ok(vec![SubMsg::new(weight_msg), pull_msg])
+}
+
+// group or proxy which asked to join this one is registered right away, anybody else gets a proxy
+fn admit_msgs(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    config: &Config,
+    addr: &Addr,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let is_subgroup = querier
+        .query_wasm_smart::<GroupInfoResponse>(addr, &QueryMsg::GroupInfo {})
+        .is_ok_and(|info| info.parent.as_deref() == Some(env.contract.address.as_str()));
+    if is_subgroup {
+        return Ok(subgroup_msgs(storage, querier, env, config, addr)?);
+    }
+
+    let is_federated = proxy::state::PENDING_GROUPS
+        .query(querier, addr.clone(), &env.contract.address)
+        .is_ok_and(|share| share.is_some());
+    if is_federated {
+        return federated_msgs(storage, querier, env, config, addr);
+    }
+
+    Ok(vec![proxy_instantiate_msg(storage, env, config, addr)?])
+}
+
+fn subgroup_msgs(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    config: &Config,
+    addr: &Addr,
+) -> StdResult<Vec<SubMsg>> {
+    MEMBERS.save(storage, addr, &Empty {})?;
+    PROXIES.save(storage, addr, addr)?;
+    SUBGROUPS.save(storage, addr, &Empty {})?;
+    MEMBER_COUNT.update(storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+
+    let weight: TotalWeightResponse =
+        querier.query_wasm_smart(addr, &QueryMsg::TotalWeight { at_height: None })?;
+    let diffs = vec![update_member_weight(
+        storage,
+        env.block.height,
+        addr,
+        Some(weight.weight),
+    )?];
+
+    let accepted_msg = ExecMsg::ParentAccepted {
+        distribution_contract: config.distribution_contract.to_string(),
+    };
+    let accepted_msg = WasmMsg::Execute {
+        contract_addr: addr.to_string(),
+        msg: to_json_binary(&accepted_msg)?,
+        funds: vec![],
+    };
+
+    let mut msgs = vec![SubMsg::new(accepted_msg)];
+    msgs.extend(mint_badge_msg(storage, env, addr, addr)?.map(SubMsg::new));
+    msgs.extend(member_changed_msgs(storage, diffs.clone())?);
+    msgs.extend(parent_msgs(storage, querier, env, &diffs)?);
+    Ok(msgs)
+}
+
+// proxy of another group keeps its home, it only starts sending a share of donations here
+fn federated_msgs(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    config: &Config,
+    proxy: &Addr,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let owner = proxy::state::OWNER.query(querier, proxy.clone())?;
+    ensure!(!PROXIES.has(storage, &owner), ContractError::AlreadyAMember);
+
+    MEMBERS.save(storage, proxy, &Empty {})?;
+    PROXIES.save(storage, &owner, proxy)?;
+    FEDERATED_PROXIES.save(storage, proxy, &Empty {})?;
+    MEMBER_COUNT.update(storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+
+    let weight = proxy::state::WEIGHT.query(querier, proxy.clone())?;
+    let diffs = vec![update_member_weight(
+        storage,
+        env.block.height,
+        &owner,
+        Some(weight),
+    )?];
+
+    let accepted_msg = ProxyExecMsg::GroupAccepted {
+        distribution_contract: config.distribution_contract.to_string(),
+    };
+    let accepted_msg = WasmMsg::Execute {
+        contract_addr: proxy.to_string(),
+        msg: to_json_binary(&accepted_msg)?,
+        funds: vec![],
+    };
+
+    let mut msgs = vec![SubMsg::new(accepted_msg)];
+    msgs.extend(mint_badge_msg(storage, env, &owner, proxy)?.map(SubMsg::new));
+    msgs.extend(member_changed_msgs(storage, diffs.clone())?);
+    msgs.extend(parent_msgs(storage, querier, env, &diffs)?);
+    Ok(msgs)
+}
+
+// candidates who got a proxy in the meantime (by ownership transfer) are skipped
+fn next_waitlisted(storage: &mut dyn Storage) -> StdResult<Option<Addr>> {
+    let mut waitlist = WAITLIST.may_load(storage)?.unwrap_or_default();
+    let position = waitlist
+        .iter()
+        .position(|candidate| !PROXIES.has(storage, candidate));
+    let candidate = position.map(|position| waitlist[position].clone());
+
+    waitlist.drain(..position.map_or(waitlist.len(), |position| position + 1));
+    WAITLIST.save(storage, &waitlist)?;
+
+    Ok(candidate)
+}
+
+pub fn proxy_receipts(
+    storage: &dyn Storage,
+    config: &Config,
+) -> StdResult<Option<ProxyReceiptsConfig>> {
+    let receipts = RECEIPT_CONTRACT
+        .may_load(storage)?
+        .zip(config.receipt_threshold)
+        .map(|(contract, threshold)| ProxyReceiptsConfig {
+            contract: contract.into_string(),
+            threshold,
+        });
+    Ok(receipts)
+}
+
+// keeps cw4 weights and their total in sync, None => owner left the group
+pub fn update_member_weight(
+    storage: &mut dyn Storage,
+    height: u64,
+    owner: &Addr,
+    weight: Option<u64>,
+) -> StdResult<MemberDiff> {
+    let old_weight = WEIGHTS.may_load(storage, owner)?;
+    match weight {
+        Some(weight) => WEIGHTS.save(storage, owner, &weight, height)?,
+        None => WEIGHTS.remove(storage, owner, height)?,
+    }
+
+    TOTAL_WEIGHT.update(storage, height, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default() - old_weight.unwrap_or_default() + weight.unwrap_or_default())
+    })?;
+
+    Ok(MemberDiff::new(owner, old_weight, weight))
+}
+
+pub fn member_changed_msgs(
+    storage: &dyn Storage,
+    diffs: Vec<MemberDiff>,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = MemberChangedHookMsg::new(diffs);
+    HOOKS.prepare_hooks(storage, |hook| {
+        msg.clone().into_cosmos_msg(hook).map(SubMsg::new)
+    })
+}
+
+// badge token id is the proxy address, nothing is minted when the group has no badges
+pub fn mint_badge_msg(
+    storage: &dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    proxy: &Addr,
+) -> StdResult<Option<WasmMsg>> {
+    let Some(badge_contract) = BADGE_CONTRACT.may_load(storage)? else {
+        return Ok(None);
+    };
+
+    let mint_msg = BadgeExecMsg::Mint {
+        token_id: proxy.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: BadgeMetadata {
+            proxy: proxy.to_string(),
+            joined_at: env.block.time.seconds(),
+        },
+    };
+    let mint_msg = WasmMsg::Execute {
+        contract_addr: badge_contract.into_string(),
+        msg: to_json_binary(&mint_msg)?,
+        funds: vec![],
+    };
+
+    Ok(Some(mint_msg))
+}
+
+fn burn_badge_msg(storage: &dyn Storage, proxy: &Addr) -> StdResult<Option<WasmMsg>> {
+    let Some(badge_contract) = BADGE_CONTRACT.may_load(storage)? else {
+        return Ok(None);
+    };
+
+    let burn_msg = BadgeExecMsg::Burn {
+        token_id: proxy.to_string(),
+    };
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: badge_contract.into_string(),
+        msg: to_json_binary(&burn_msg)?,
+        funds: vec![],
+    };
+
+    Ok(Some(burn_msg))
+}
+
+// every proxy holds its own copy of the pause state
+// subgroups are paused by their own guardian and federated proxies by their home group
+fn push_paused(storage: &dyn Storage, paused: &PauseState) -> StdResult<Vec<WasmMsg>> {
+    MEMBERS
+        .keys(storage, None, None, Order::Ascending)
+        .filter(|proxy| {
+            proxy.as_ref().map_or(true, |proxy| {
+                !SUBGROUPS.has(storage, proxy) && !FEDERATED_PROXIES.has(storage, proxy)
+            })
+        })
+        .map(|proxy| set_paused_msg(&proxy?, paused))
+        .collect()
+}
 
 pub fn propose_member(
     deps: DepsMut,
@@ -22,17 +398,25 @@ pub fn propose_member(
         MEMBERS.has(deps.storage, &info.sender),
         ContractError::Unauthorized
     );
+    ensure_not_paused(deps.storage, PausableAction::Propose)?;
 
     // validate address of the new member
     let addr = deps.api.addr_validate(&addr)?;
 
-    // check if the sender is already a member
-    // just think it like a normal for loop
-    for member in MEMBERS.range(deps.storage, None, None, Order::Ascending) {
-        let (member, _) = member?; // get proxy contract address
-        let owner = proxy::state::OWNER.query(&deps.querier, member)?; // get the owner of the proxy contract
-        ensure!(owner != addr, ContractError::AlreadyAMember);
-    }
+    // check if the new member already owns a proxy in the group
+    ensure!(
+        !PROXIES.has(deps.storage, &addr),
+        ContractError::AlreadyAMember
+    );
+
+    // accepted already, waits for a free place
+    ensure!(
+        !WAITLIST
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .contains(&addr),
+        ContractError::AlreadyWaitlisted
+    );
 
     // check if the sender has already voted for this new member
     ensure!(
@@ -40,18 +424,22 @@ pub fn propose_member(
         ContractError::AlreadyVoted
     );
 
+    // get the minimal acceptances from CONFIG
+    let config = CONFIG.load(deps.storage)?;
+
     // if pass through all the checks, then store to VOTES and update PROPOSALS
 
     // use may_load here because maybe this the first time that the new member is proposed
     let number_votes_of_new_member = PROPOSALS.may_load(deps.storage, &addr)?.unwrap_or(0) + 1;
     VOTES.save(deps.storage, (&info.sender, &addr), &Empty {})?;
 
-    // get the minimal acceptances from CONFIG
-    let config = CONFIG.load(deps.storage)?;
     let minimal_acceptances = config.minimal_acceptances;
 
     // it means that the new member need more votes to be accepted
     if number_votes_of_new_member < minimal_acceptances {
+        if number_votes_of_new_member == 1 {
+            open_proposal(deps.storage, &PENDING_ADDITIONS)?;
+        }
         PROPOSALS.save(deps.storage, &addr, &number_votes_of_new_member)?;
 
         let resp = Response::new()
@@ -66,33 +454,640 @@ pub fn propose_member(
     // so if below code is executed, it means that the new member is accepted
     // then we create for him a proxy contract
 
-    // what happen if not delete?
-    PROPOSALS.remove(deps.storage, &addr);
+    // votes are removed, so the member can be voted out later with the same tables
+    clear_proposal(deps.storage, &addr, &PENDING_ADDITIONS)?;
 
-    let proxy_init_msg = ProxyInstantiateMsg {
-        owner: addr.to_string(),
-        weight: config.starting_weight,
-        denom: config.denom,
-        direct_part: config.direct_part,
-        distribution_contract: config.distribution_contract.into_string(),
-        membership_contract: env.contract.address.to_string(),
-        halftime: config.halftime,
-    };
+    // group is full => candidate waits until somebody leaves
+    let member_count = MEMBER_COUNT.load(deps.storage)?;
+    if config.max_members.is_some_and(|max| member_count >= max) {
+        let mut waitlist = WAITLIST.may_load(deps.storage)?.unwrap_or_default();
+        waitlist.push(addr.clone());
+        WAITLIST.save(deps.storage, &waitlist)?;
 
-    let proxy_init_msg = WasmMsg::Instantiate {
-        admin: Some(env.contract.address.into_string()),
-        code_id: config.proxy_code_id,
-        msg: to_json_binary(&proxy_init_msg)?,
+        let resp = Response::new()
+            .add_attribute("action", "propose member")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("new_member", addr.as_str())
+            .add_attribute("waitlist_position", waitlist.len().to_string());
+
+        return Ok(resp);
+    }
+
+    let admit_msgs = admit_msgs(deps.storage, &deps.querier, &env, &config, &addr)?;
+    let mut resp = Response::new()
+        .add_submessages(admit_msgs)
+        .add_attribute("action", "propose member")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("new_member", addr.as_str());
+
+    // new proxies report their data in the instantiation reply, others joined already
+    if MEMBERS.has(deps.storage, &addr) {
+        let owner = if SUBGROUPS.has(deps.storage, &addr) {
+            addr.clone()
+        } else {
+            proxy::state::OWNER.query(&deps.querier, addr.clone())?
+        };
+        let data = ProposeMemberData {
+            owner_addr: owner.into_string(),
+            proxy_addr: addr.to_string(),
+        };
+        resp = resp.set_data(to_json_binary(&data)?);
+    }
+
+    Ok(resp)
+}
+
+pub fn ownership_transferred(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    previous_owner: String,
+    new_owner: String,
+) -> Result<Response, ContractError> {
+    let previous_owner = deps.api.addr_validate(&previous_owner)?;
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+
+    // only the proxy of the previous owner can report the transfer
+    let proxy = PROXIES.may_load(deps.storage, &previous_owner)?;
+    ensure!(
+        proxy.as_ref() == Some(&info.sender),
+        ContractError::Unauthorized
+    );
+
+    // one owner can not have two proxies in the group
+    ensure!(
+        !PROXIES.has(deps.storage, &new_owner),
+        ContractError::AlreadyAMember
+    );
+
+    PROXIES.remove(deps.storage, &previous_owner);
+    PROXIES.save(deps.storage, &new_owner, &info.sender)?;
+
+    let weight = WEIGHTS.may_load(deps.storage, &previous_owner)?;
+    let diffs = vec![
+        update_member_weight(deps.storage, env.block.height, &previous_owner, None)?,
+        update_member_weight(deps.storage, env.block.height, &new_owner, weight)?,
+    ];
+
+    // previous owner left the group, badge is issued again for the new one
+    let burn_msg = burn_badge_msg(deps.storage, &info.sender)?;
+    let mint_msg = mint_badge_msg(deps.storage, &env, &new_owner, &info.sender)?;
+
+    let resp = Response::new()
+        .add_messages(burn_msg)
+        .add_messages(mint_msg)
+        .add_submessages(member_changed_msgs(deps.storage, diffs)?)
+        .add_attribute("action", "ownership transferred")
+        .add_attribute("proxy", info.sender.as_str())
+        .add_attribute("previous_owner", previous_owner.as_str())
+        .add_attribute("new_owner", new_owner.as_str());
+    Ok(resp)
+}
+
+pub fn pause(
+    deps: DepsMut,
+    info: MessageInfo,
+    actions: Option<Vec<PausableAction>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        config.guardian.as_ref() == Some(&info.sender),
+        ContractError::Unauthorized
+    );
+
+    let mut paused = PAUSED.may_load(deps.storage)?.unwrap_or_default();
+    match actions {
+        None => paused.all = true,
+        Some(actions) => {
+            for action in actions {
+                if !paused.actions.contains(&action) {
+                    paused.actions.push(action);
+                }
+            }
+        }
+    }
+    PAUSED.save(deps.storage, &paused)?;
+
+    // votes given before this pause don't count for unpausing it
+    UNPAUSE_VOTES.clear(deps.storage);
+
+    let resp = Response::new()
+        .add_messages(push_paused(deps.storage, &paused)?)
+        .add_attribute("action", "pause")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("all", paused.all.to_string());
+    Ok(resp)
+}
+
+pub fn propose_unpause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    ensure!(
+        MEMBERS.has(deps.storage, &info.sender),
+        ContractError::Unauthorized
+    );
+
+    let paused = PAUSED.may_load(deps.storage)?.unwrap_or_default();
+    ensure!(paused != PauseState::default(), ContractError::NotPaused);
+
+    ensure!(
+        !UNPAUSE_VOTES.has(deps.storage, &info.sender),
+        ContractError::AlreadyVoted
+    );
+    UNPAUSE_VOTES.save(deps.storage, &info.sender, &Empty {})?;
+
+    let votes = UNPAUSE_VOTES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count() as u64;
+
+    let resp = Response::new()
+        .add_attribute("action", "propose unpause")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("number_of_votes", votes.to_string());
+
+    let config = CONFIG.load(deps.storage)?;
+    if votes < config.minimal_acceptances {
+        return Ok(resp);
+    }
+
+    UNPAUSE_VOTES.clear(deps.storage);
+    PAUSED.remove(deps.storage);
+
+    let resp = resp
+        .add_messages(push_paused(deps.storage, &PauseState::default())?)
+        .add_attribute("unpaused", "yes");
+    Ok(resp)
+}
+
+// same voting as for new members, `member` is the owner address of the proxy to remove
+pub fn propose_removal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    member: String,
+    reason: String,
+) -> Result<Response, ContractError> {
+    ensure!(
+        MEMBERS.has(deps.storage, &info.sender),
+        ContractError::Unauthorized
+    );
+    ensure_not_paused(deps.storage, PausableAction::Propose)?;
+
+    let member = deps.api.addr_validate(&member)?;
+    let proxy = PROXIES
+        .may_load(deps.storage, &member)?
+        .ok_or(ContractError::NotAMember)?;
+
+    // member can not vote on his own removal
+    ensure!(proxy != info.sender, ContractError::Unauthorized);
+    ensure!(
+        !VOTES.has(deps.storage, (&info.sender, &member)),
+        ContractError::AlreadyVoted
+    );
+
+    let number_of_votes = PROPOSALS.may_load(deps.storage, &member)?.unwrap_or(0) + 1;
+    VOTES.save(deps.storage, (&info.sender, &member), &Empty {})?;
+
+    let resp = Response::new()
+        .add_attribute("action", "propose removal")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("member", member.as_str())
+        .add_attribute("reason", reason);
+
+    let config = CONFIG.load(deps.storage)?;
+    if number_of_votes < config.minimal_acceptances {
+        if number_of_votes == 1 {
+            open_proposal(deps.storage, &PENDING_REMOVALS)?;
+        }
+        PROPOSALS.save(deps.storage, &member, &number_of_votes)?;
+        return Ok(resp.add_attribute("number_of_votes", number_of_votes.to_string()));
+    }
+
+    clear_proposal(deps.storage, &member, &PENDING_REMOVALS)?;
+    remove_member(deps, &env, &config, &member, &proxy, resp)
+}
+
+// owner leaves on his own, the place is freed the same way as by a removal
+pub fn leave(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    ensure!(
+        MEMBERS.has(deps.storage, &info.sender) && !SUBGROUPS.has(deps.storage, &info.sender),
+        ContractError::Unauthorized
+    );
+
+    let member = proxy::state::OWNER.query(&deps.querier, info.sender.clone())?;
+    let config = CONFIG.load(deps.storage)?;
+    clear_proposal(deps.storage, &member, &PENDING_REMOVALS)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "leave")
+        .add_attribute("member", member.as_str());
+    remove_member(deps, &env, &config, &member, &info.sender, resp)
+}
+
+fn remove_member(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    member: &Addr,
+    proxy: &Addr,
+    resp: Response,
+) -> Result<Response, ContractError> {
+    MEMBERS.remove(deps.storage, proxy);
+    PROXIES.remove(deps.storage, member);
+    MEMBER_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count - 1) })?;
+    let diff = update_member_weight(deps.storage, env.block.height, member, None)?;
+    UNPAUSE_VOTES.remove(deps.storage, proxy);
+
+    // proxy closes itself and gives up its distribution weight, accrued funds stay withdrawable
+    // subgroup does the same with its weight in our distribution, its own members stay
+    FEDERATED_PROXIES.remove(deps.storage, proxy);
+    let remove_msg = if SUBGROUPS.has(deps.storage, proxy) {
+        SUBGROUPS.remove(deps.storage, proxy);
+        to_json_binary(&ExecMsg::ParentRemoved {})?
+    } else {
+        to_json_binary(&ProxyExecMsg::Remove {})?
+    };
+    let remove_msg = WasmMsg::Execute {
+        contract_addr: proxy.to_string(),
+        msg: remove_msg,
         funds: vec![],
-        label: format!("{} proxy", addr),
     };
 
-    let proxy_init_msg = SubMsg::reply_on_success(proxy_init_msg, PROXY_INSTANTIATION_REPLY_ID);
+    let diffs = vec![diff];
+    let mut resp = resp
+        .add_message(remove_msg)
+        .add_submessages(member_changed_msgs(deps.storage, diffs.clone())?)
+        .add_submessages(parent_msgs(deps.storage, &deps.querier, env, &diffs)?)
+        .add_messages(burn_badge_msg(deps.storage, proxy)?)
+        .add_attribute("removed_proxy", proxy.as_str());
+
+    // the place goes to the first waitlisted candidate
+    if let Some(candidate) = next_waitlisted(deps.storage)? {
+        resp = resp
+            .add_submessages(admit_msgs(
+                deps.storage,
+                &deps.querier,
+                env,
+                config,
+                &candidate,
+            )?)
+            .add_attribute("admitted", candidate.as_str());
+    }
+
+    Ok(resp)
+}
+
+pub fn weight_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    weight: u64,
+) -> Result<Response, ContractError> {
+    ensure!(
+        MEMBERS.has(deps.storage, &info.sender),
+        ContractError::Unauthorized
+    );
+
+    let owner = if SUBGROUPS.has(deps.storage, &info.sender) {
+        info.sender.clone()
+    } else {
+        proxy::state::OWNER.query(&deps.querier, info.sender.clone())?
+    };
+    let diffs = vec![update_member_weight(
+        deps.storage,
+        env.block.height,
+        &owner,
+        Some(weight),
+    )?];
+
+    let resp = Response::new()
+        .add_submessages(member_changed_msgs(deps.storage, diffs.clone())?)
+        .add_submessages(parent_msgs(deps.storage, &deps.querier, &env, &diffs)?)
+        .add_attribute("action", "weight changed")
+        .add_attribute("member", owner.as_str())
+        .add_attribute("weight", weight.to_string());
+    Ok(resp)
+}
+
+// own member proxies only, federated proxies count in their home group and subgroups in theirs
+fn ensure_own_proxy(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    ensure!(
+        MEMBERS.has(storage, sender)
+            && !FEDERATED_PROXIES.has(storage, sender)
+            && !SUBGROUPS.has(storage, sender),
+        ContractError::Unauthorized
+    );
+    Ok(())
+}
+
+pub fn donated(deps: DepsMut, info: MessageInfo, amount: Coin) -> Result<Response, ContractError> {
+    ensure_own_proxy(deps.storage, &info.sender)?;
+
+    TOTAL_DONATED.update(deps.storage, &amount.denom, |donated| -> StdResult<_> {
+        Ok(donated.unwrap_or_default() + amount.amount)
+    })?;
 
     let resp = Response::new()
-        .add_submessage(proxy_init_msg)
-        .add_attribute("action", "propose member")
+        .add_attribute("action", "donated")
+        .add_attribute("proxy", info.sender.as_str())
+        .add_attribute("amount", amount.to_string());
+    Ok(resp)
+}
+
+pub fn decayed(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    ensure_own_proxy(deps.storage, &info.sender)?;
+
+    LAST_DECAY.save(deps.storage, &env.block.time.seconds())?;
+
+    let resp = Response::new()
+        .add_attribute("action", "decayed")
+        .add_attribute("proxy", info.sender.as_str());
+    Ok(resp)
+}
+
+pub fn add_hook(deps: DepsMut, info: MessageInfo, addr: String) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        config.guardian.as_ref() == Some(&info.sender),
+        ContractError::Unauthorized
+    );
+
+    let hook = deps.api.addr_validate(&addr)?;
+    HOOKS.add_hook(deps.storage, hook)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "add_hook")
+        .add_attribute("hook", addr);
+    Ok(resp)
+}
+
+pub fn remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        config.guardian.as_ref() == Some(&info.sender),
+        ContractError::Unauthorized
+    );
+
+    let hook = deps.api.addr_validate(&addr)?;
+    HOOKS.remove_hook(deps.storage, hook)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "remove_hook")
+        .add_attribute("hook", addr);
+    Ok(resp)
+}
+
+// proposer's vote counts as the first acceptance
+// treasury must not act on the group itself, members, or the contracts the group controls
+fn ensure_spend_allowed(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    msgs: &[SpendMsg],
+) -> Result<(), ContractError> {
+    for msg in msgs {
+        match msg {
+            SpendMsg::BankSend { to_address, .. } => {
+                deps.api.addr_validate(to_address)?;
+            }
+            SpendMsg::WasmExecute { contract_addr, .. } => {
+                let target = deps.api.addr_validate(contract_addr)?;
+                let protected = target == env.contract.address
+                    || target == config.distribution_contract
+                    || MEMBERS.has(deps.storage, &target)
+                    || BADGE_CONTRACT.may_load(deps.storage)?.as_ref() == Some(&target)
+                    || RECEIPT_CONTRACT.may_load(deps.storage)?.as_ref() == Some(&target)
+                    || PARENT
+                        .may_load(deps.storage)?
+                        .is_some_and(|parent| parent.membership == target);
+                ensure!(
+                    !protected,
+                    ContractError::SpendTargetNotAllowed(target.into_string())
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn propose_spend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<SpendMsg>,
+    description: String,
+) -> Result<Response, ContractError> {
+    ensure!(
+        MEMBERS.has(deps.storage, &info.sender),
+        ContractError::Unauthorized
+    );
+    ensure_not_paused(deps.storage, PausableAction::Propose)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    ensure_spend_allowed(deps.as_ref(), &env, &config, &msgs)?;
+
+    let id = NEXT_SPEND_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_SPEND_ID.save(deps.storage, &(id + 1))?;
+
+    let expires = env.block.time.seconds() + config.spend_voting_period;
+    SPEND_PROPOSALS.save(
+        deps.storage,
+        id,
+        &SpendProposal {
+            description,
+            msgs,
+            votes: 1,
+            status: SpendStatus::Open,
+            expires,
+        },
+    )?;
+    SPEND_VOTES.save(deps.storage, (id, &info.sender), &Empty {})?;
+
+    let resp = Response::new()
+        .add_attribute("action", "propose spend")
         .add_attribute("sender", info.sender.as_str())
-        .add_attribute("new_member", addr.as_str());
+        .add_attribute("id", id.to_string())
+        .add_attribute("expires", expires.to_string());
+    Ok(resp)
+}
+
+pub fn vote_spend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    ensure!(
+        MEMBERS.has(deps.storage, &info.sender),
+        ContractError::Unauthorized
+    );
+    ensure_not_paused(deps.storage, PausableAction::Propose)?;
+
+    let mut proposal = SPEND_PROPOSALS.load(deps.storage, id)?;
+    ensure!(
+        proposal.status == SpendStatus::Open,
+        ContractError::SpendExecuted(id)
+    );
+    ensure!(
+        env.block.time.seconds() < proposal.expires,
+        ContractError::SpendExpired(id)
+    );
+    ensure!(
+        !SPEND_VOTES.has(deps.storage, (id, &info.sender)),
+        ContractError::AlreadyVoted
+    );
+
+    SPEND_VOTES.save(deps.storage, (id, &info.sender), &Empty {})?;
+
+    // votes of removed or departed members do not count anymore
+    let voters = SPEND_VOTES
+        .prefix(id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    proposal.votes = voters
+        .iter()
+        .filter(|voter| MEMBERS.has(deps.storage, voter))
+        .count() as u64;
+
+    let resp = Response::new()
+        .add_attribute("action", "vote spend")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("id", id.to_string())
+        .add_attribute("votes", proposal.votes.to_string());
+
+    let config = CONFIG.load(deps.storage)?;
+    if proposal.votes < config.minimal_acceptances {
+        SPEND_PROPOSALS.save(deps.storage, id, &proposal)?;
+        return Ok(resp);
+    }
+
+    // targets could have joined the group since the proposal, so they are checked again
+    ensure_spend_allowed(deps.as_ref(), &env, &config, &proposal.msgs)?;
+
+    // whole batch is executed by the treasury, any failing message reverts the vote
+    proposal.status = SpendStatus::Executed;
+    SPEND_PROPOSALS.save(deps.storage, id, &proposal)?;
+
+    let msgs = proposal.msgs.into_iter().map(CosmosMsg::from);
+    let resp = resp.add_messages(msgs).add_attribute("executed", "yes");
+    Ok(resp)
+}
+
+pub fn join_parent(
+    deps: DepsMut,
+    info: MessageInfo,
+    parent: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        config.guardian.as_ref() == Some(&info.sender),
+        ContractError::Unauthorized
+    );
+
+    // asking another group is fine until one of them accepts
+    let nested = PARENT
+        .may_load(deps.storage)?
+        .is_some_and(|parent| parent.distribution.is_some());
+    ensure!(!nested, ContractError::AlreadyNested);
+
+    let parent = deps.api.addr_validate(&parent)?;
+    PARENT.save(
+        deps.storage,
+        &Parent {
+            membership: parent.clone(),
+            distribution: None,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "join parent")
+        .add_attribute("parent", parent.as_str());
+    Ok(resp)
+}
+
+pub fn parent_accepted(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    distribution_contract: String,
+) -> Result<Response, ContractError> {
+    let mut parent = PARENT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized)?;
+    ensure!(
+        parent.membership == info.sender && parent.distribution.is_none(),
+        ContractError::Unauthorized
+    );
+
+    let distribution = deps.api.addr_validate(&distribution_contract)?;
+    parent.distribution = Some(distribution.clone());
+    PARENT.save(deps.storage, &parent)?;
+
+    // whole group weight enters the parent distribution
+    let total = TOTAL_WEIGHT.load(deps.storage)?;
+    let pull_msg = pull_msg(
+        deps.storage,
+        &deps.querier,
+        &env,
+        &distribution,
+        0,
+        total as i64,
+    )?;
+
+    let resp = Response::new()
+        .add_submessage(pull_msg)
+        .add_attribute("action", "parent accepted")
+        .add_attribute("parent", info.sender.as_str());
+    Ok(resp)
+}
+
+pub fn parent_removed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let parent = PARENT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NotNested)?;
+    ensure!(
+        parent.membership == info.sender,
+        ContractError::Unauthorized
+    );
+    let distribution = parent.distribution.ok_or(ContractError::NotNested)?;
+
+    PARENT.remove(deps.storage);
+
+    // last pull gives up the weight, the accrued share still comes down
+    let total = TOTAL_WEIGHT.load(deps.storage)?;
+    let pull_msg = pull_msg(
+        deps.storage,
+        &deps.querier,
+        &env,
+        &distribution,
+        total,
+        -(total as i64),
+    )?;
+
+    let resp = Response::new()
+        .add_submessage(pull_msg)
+        .add_attribute("action", "parent removed")
+        .add_attribute("parent", info.sender.as_str());
+    Ok(resp)
+}
+
+pub fn cascade(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let distribution = PARENT
+        .may_load(deps.storage)?
+        .and_then(|parent| parent.distribution)
+        .ok_or(ContractError::NotNested)?;
+
+    let total = TOTAL_WEIGHT.load(deps.storage)?;
+    let pull_msg = pull_msg(deps.storage, &deps.querier, &env, &distribution, total, 0)?;
+
+    let resp = Response::new()
+        .add_submessage(pull_msg)
+        .add_attribute("action", "cascade");
     Ok(resp)
 }
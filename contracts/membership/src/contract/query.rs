@@ -1,8 +1,228 @@
-use cosmwasm_std::{Addr, Deps, StdResult};
+use common::msg::{DistributionQueryMsg, PauseState, PendingRewardsResponse};
+use cosmwasm_std::{coin, Addr, Deps, Env, Order, StdResult};
+use cw4::{Member, MemberListResponse, MemberResponse, TotalWeightResponse};
+use cw_storage_plus::Bound;
 
-use crate::{msg::IsMemberResponse, state::MEMBERS};
+use crate::{
+    msg::{
+        GroupInfoResponse, GroupStatsResponse, IsMemberResponse, ListSpendProposalsResponse,
+        MemberDetailsResponse, SpendProposalResponse, WaitlistEntry, WaitlistResponse,
+    },
+    state::{
+        SpendProposal, SpendStatus, BADGE_CONTRACT, CONFIG, LAST_DECAY, MEMBERS, MEMBER_COUNT,
+        PARENT, PAUSED, PENDING_ADDITIONS, PENDING_REMOVALS, PROXIES, RECEIPT_CONTRACT,
+        SPEND_PROPOSALS, SUBGROUPS, TOTAL_DONATED, TOTAL_WEIGHT, WAITLIST, WEIGHTS,
+    },
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 pub fn is_member(deps: Deps, addr: String) -> StdResult<IsMemberResponse> {
     let is_member = MEMBERS.has(deps.storage, &Addr::unchecked(addr));
     Ok(IsMemberResponse { is_member })
 }
+
+pub fn paused(deps: Deps) -> StdResult<PauseState> {
+    Ok(PAUSED.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn group_info(deps: Deps) -> StdResult<GroupInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(GroupInfoResponse {
+        member_count: MEMBER_COUNT.load(deps.storage)?,
+        max_members: config.max_members,
+        minimal_acceptances: config.minimal_acceptances,
+        badge_contract: BADGE_CONTRACT
+            .may_load(deps.storage)?
+            .map(Addr::into_string),
+        receipt_contract: RECEIPT_CONTRACT
+            .may_load(deps.storage)?
+            .map(Addr::into_string),
+        distribution_contract: config.distribution_contract.into_string(),
+        parent: PARENT
+            .may_load(deps.storage)?
+            .map(|parent| parent.membership.into_string()),
+    })
+}
+
+pub fn waitlist(deps: Deps) -> StdResult<WaitlistResponse> {
+    let candidates = WAITLIST
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .zip(1..)
+        .map(|(addr, position)| WaitlistEntry {
+            addr: addr.into_string(),
+            position,
+        })
+        .collect();
+
+    Ok(WaitlistResponse { candidates })
+}
+
+pub fn member(deps: Deps, addr: String, at_height: Option<u64>) -> StdResult<MemberResponse> {
+    let addr = Addr::unchecked(addr);
+    let weight = match at_height {
+        Some(height) => WEIGHTS.may_load_at_height(deps.storage, &addr, height)?,
+        None => WEIGHTS.may_load(deps.storage, &addr)?,
+    };
+    Ok(MemberResponse { weight })
+}
+
+pub fn list_members(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MemberListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let members = WEIGHTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|member| -> StdResult<_> {
+            let (addr, weight) = member?;
+            Ok(Member {
+                addr: addr.into_string(),
+                weight,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(MemberListResponse { members })
+}
+
+pub fn total_weight(deps: Deps, at_height: Option<u64>) -> StdResult<TotalWeightResponse> {
+    let weight = match at_height {
+        Some(height) => TOTAL_WEIGHT
+            .may_load_at_height(deps.storage, height)?
+            .unwrap_or_default(),
+        None => TOTAL_WEIGHT.load(deps.storage)?,
+    };
+    Ok(TotalWeightResponse { weight })
+}
+
+fn spend_status(env: &Env, proposal: &SpendProposal) -> SpendStatus {
+    match proposal.status {
+        SpendStatus::Open if env.block.time.seconds() >= proposal.expires => SpendStatus::Expired,
+        ref status => status.clone(),
+    }
+}
+
+fn spend_proposal_response(env: &Env, id: u64, proposal: SpendProposal) -> SpendProposalResponse {
+    SpendProposalResponse {
+        id,
+        status: spend_status(env, &proposal),
+        description: proposal.description,
+        msgs: proposal.msgs,
+        votes: proposal.votes,
+        expires: proposal.expires,
+    }
+}
+
+pub fn spend_proposal(deps: Deps, env: Env, id: u64) -> StdResult<SpendProposalResponse> {
+    let proposal = SPEND_PROPOSALS.load(deps.storage, id)?;
+    Ok(spend_proposal_response(&env, id, proposal))
+}
+
+pub fn list_spend_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListSpendProposalsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let proposals = SPEND_PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|proposal| -> StdResult<_> {
+            let (id, proposal) = proposal?;
+            Ok(spend_proposal_response(&env, id, proposal))
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListSpendProposalsResponse { proposals })
+}
+
+pub fn group_stats(deps: Deps, env: Env) -> StdResult<GroupStatsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let denoms = std::iter::once(config.denom.clone())
+        .chain(config.ibc_denoms.iter().map(|ibc| ibc.denom.clone()));
+    let total_donated = denoms
+        .map(|denom| {
+            let amount = TOTAL_DONATED
+                .may_load(deps.storage, &denom)?
+                .unwrap_or_default();
+            Ok(coin(amount.u128(), denom))
+        })
+        .collect::<StdResult<_>>()?;
+
+    // ids grow with the expiry, so only proposals of the last voting period can still be open
+    let mut open_spend_proposals = 0;
+    for proposal in SPEND_PROPOSALS.range(deps.storage, None, None, Order::Descending) {
+        let (_, proposal) = proposal?;
+        if proposal.expires <= env.block.time.seconds() {
+            break;
+        }
+        if spend_status(&env, &proposal) == SpendStatus::Open {
+            open_spend_proposals += 1;
+        }
+    }
+
+    Ok(GroupStatsResponse {
+        member_count: MEMBER_COUNT.load(deps.storage)?,
+        total_weight: TOTAL_WEIGHT.load(deps.storage)?,
+        total_donated,
+        distribution_balance: deps
+            .querier
+            .query_all_balances(&config.distribution_contract)?,
+        pending_additions: PENDING_ADDITIONS
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        pending_removals: PENDING_REMOVALS.may_load(deps.storage)?.unwrap_or_default(),
+        open_spend_proposals,
+        last_decay: LAST_DECAY.may_load(deps.storage)?,
+    })
+}
+
+// member groups have no proxy state, only their weight in this group
+pub fn member_details(deps: Deps, owner: String) -> StdResult<MemberDetailsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let owner = Addr::unchecked(owner);
+    let proxy = PROXIES.load(deps.storage, &owner)?;
+    let weight = WEIGHTS.may_load(deps.storage, &owner)?.unwrap_or_default();
+
+    let rewards: PendingRewardsResponse = deps.querier.query_wasm_smart(
+        &config.distribution_contract,
+        &DistributionQueryMsg::PendingRewards {
+            proxy: proxy.to_string(),
+        },
+    )?;
+
+    if SUBGROUPS.has(deps.storage, &proxy) {
+        return Ok(MemberDetailsResponse {
+            proxy: proxy.into_string(),
+            weight,
+            donations: None,
+            last_updated: None,
+            is_closed: false,
+            pending_rewards: rewards.amount,
+        });
+    }
+
+    Ok(MemberDetailsResponse {
+        weight,
+        donations: Some(proxy::state::DONATIONS.query(&deps.querier, proxy.clone())?),
+        last_updated: Some(proxy::state::LAST_UPDATED.query(&deps.querier, proxy.clone())?),
+        is_closed: proxy::state::CONFIG
+            .query(&deps.querier, proxy.clone())?
+            .is_closed,
+        pending_rewards: rewards.amount,
+        proxy: proxy.into_string(),
+    })
+}
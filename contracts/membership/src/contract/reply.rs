@@ -1,32 +1,118 @@
 use common::msg::ProposeMemberData;
 use cosmwasm_std::{
-    to_json_binary, Addr, DepsMut, Empty, Order, Response, StdError, StdResult, SubMsgResponse,
+    coins, to_json_binary, Addr, DepsMut, Empty, Env, Order, Response, StdError, StdResult,
+    SubMsgResponse, WasmMsg,
 };
 use cw_utils::parse_instantiate_response_data;
 
 use crate::{
     error::ContractError,
     msg::InstantiationData,
-    state::{AWAITING_INITIAL_RESPS, MEMBERS},
+    state::{
+        AWAITING_INITIAL_RESPS, BADGE_CONTRACT, CASCADE_BALANCE, CONFIG, INITIAL_MEMBERS, MEMBERS,
+        MEMBER_COUNT, PAUSED, PROXIES, RECEIPT_CONTRACT,
+    },
 };
 
+use super::exec::{
+    member_changed_msgs, mint_badge_msg, parent_msgs, set_paused_msg, update_member_weight,
+};
+use super::initial_proxy_instantiate_msgs;
+use proxy::msg::DistribtionExecMsg;
+
+pub fn distribution_instantiated(
+    deps: DepsMut,
+    env: Env,
+    reply: Result<SubMsgResponse, String>,
+) -> Result<Response, ContractError> {
+    let response = reply.map_err(StdError::generic_err)?;
+    let data = response.data.ok_or(ContractError::MissingData)?;
+    let response = parse_instantiate_response_data(&data)?;
+    let distribution_addr = Addr::unchecked(response.contract_address);
+
+    let config = CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.distribution_contract = distribution_addr.clone();
+        Ok(config)
+    })?;
+
+    let initial_members = INITIAL_MEMBERS.load(deps.storage)?;
+    INITIAL_MEMBERS.remove(deps.storage);
+
+    let proxy_instantiate_msgs =
+        initial_proxy_instantiate_msgs(deps, &env, &config, initial_members)?;
+
+    let resp = Response::new()
+        .add_submessages(proxy_instantiate_msgs)
+        .add_attribute("distribution_addr", distribution_addr.as_str());
+
+    Ok(resp)
+}
+
+pub fn badge_instantiated(
+    deps: DepsMut,
+    reply: Result<SubMsgResponse, String>,
+) -> Result<Response, ContractError> {
+    let response = reply.map_err(StdError::generic_err)?;
+    let data = response.data.ok_or(ContractError::MissingData)?;
+    let response = parse_instantiate_response_data(&data)?;
+    let badge_addr = Addr::unchecked(response.contract_address);
+
+    BADGE_CONTRACT.save(deps.storage, &badge_addr)?;
+
+    let resp = Response::new().add_attribute("badge_addr", badge_addr.as_str());
+    Ok(resp)
+}
+
+pub fn receipt_instantiated(
+    deps: DepsMut,
+    reply: Result<SubMsgResponse, String>,
+) -> Result<Response, ContractError> {
+    let response = reply.map_err(StdError::generic_err)?;
+    let data = response.data.ok_or(ContractError::MissingData)?;
+    let response = parse_instantiate_response_data(&data)?;
+    let receipt_addr = Addr::unchecked(response.contract_address);
+
+    RECEIPT_CONTRACT.save(deps.storage, &receipt_addr)?;
+
+    let resp = Response::new().add_attribute("receipt_addr", receipt_addr.as_str());
+    Ok(resp)
+}
+
 // summarize: we will have the proxy contract address from the reply and store it into MEMBERS
 pub fn initial_proxy_instantiated(
     deps: DepsMut,
+    env: Env,
     reply: Result<SubMsgResponse, String>,
 ) -> Result<Response, ContractError> {
     let response = reply.map_err(StdError::generic_err)?;
     let data = response.data.ok_or(ContractError::MissingData)?; // CosmWasm executor uses this data field to add information of created proxy contract
     let response = parse_instantiate_response_data(&data)?;
     let proxy_addr = Addr::unchecked(response.contract_address);
+    let owner = proxy::state::OWNER.query(&deps.querier, proxy_addr.clone())?;
     MEMBERS.save(deps.storage, &proxy_addr, &cosmwasm_std::Empty {})?;
+    PROXIES.save(deps.storage, &owner, &proxy_addr)?;
+    MEMBER_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let diff = update_member_weight(
+        deps.storage,
+        env.block.height,
+        &owner,
+        Some(config.starting_weight),
+    )?;
+    let hook_msgs = member_changed_msgs(deps.storage, vec![diff])?;
+
+    let badge_msg = mint_badge_msg(deps.storage, &env, &owner, &proxy_addr)?;
 
     // means we have one less reply to wait for
     let awaiting = AWAITING_INITIAL_RESPS.load(deps.storage)? - 1;
     if awaiting > 0 {
         AWAITING_INITIAL_RESPS.save(deps.storage, &awaiting)?; // github repo saved 0 which is wrong and be fixed in the next commit
 
-        let resp = Response::new().add_attribute("proxy_addr", proxy_addr);
+        let resp = Response::new()
+            .add_messages(badge_msg)
+            .add_submessages(hook_msgs)
+            .add_attribute("proxy_addr", proxy_addr);
 
         return Ok(resp);
     }
@@ -49,6 +135,8 @@ pub fn initial_proxy_instantiated(
 
     let data = InstantiationData { members };
     let resp = Response::new()
+        .add_messages(badge_msg)
+        .add_submessages(hook_msgs)
         .add_attribute("proxy addr", proxy_addr.as_str())
         .set_data(to_json_binary(&data)?);
 
@@ -57,6 +145,7 @@ pub fn initial_proxy_instantiated(
 
 pub fn proxy_instantiated(
     deps: DepsMut,
+    env: Env,
     reply: Result<SubMsgResponse, String>,
 ) -> Result<Response, ContractError> {
     let response = reply.map_err(StdError::generic_err)?;
@@ -67,15 +156,61 @@ pub fn proxy_instantiated(
     let owner = proxy::state::OWNER.query(&deps.querier, addr.clone())?;
 
     MEMBERS.save(deps.storage, &addr, &Empty {})?;
+    PROXIES.save(deps.storage, &owner, &addr)?;
+    MEMBER_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let diffs = vec![update_member_weight(
+        deps.storage,
+        env.block.height,
+        &owner,
+        Some(config.starting_weight),
+    )?];
+    let hook_msgs = member_changed_msgs(deps.storage, diffs.clone())?;
+    let parent_msgs = parent_msgs(deps.storage, &deps.querier, &env, &diffs)?;
+
+    let badge_msg = mint_badge_msg(deps.storage, &env, &owner, &addr)?;
 
     let data = ProposeMemberData {
         owner_addr: owner.into(),
         proxy_addr: addr.to_string(),
     };
 
-    let resp = Response::new()
+    let mut resp = Response::new()
+        .add_messages(badge_msg)
+        .add_submessages(hook_msgs)
+        .add_submessages(parent_msgs)
         .add_attribute("proxy_addr", addr.as_str())
         .set_data(to_json_binary(&data)?);
 
+    // new proxy has to know the system is paused
+    if let Some(paused) = PAUSED.may_load(deps.storage)? {
+        resp = resp.add_message(set_paused_msg(&addr, &paused)?);
+    }
+
+    Ok(resp)
+}
+
+// what came from the parent distribution goes on to our own one
+pub fn cascaded(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let before = CASCADE_BALANCE.load(deps.storage)?;
+    CASCADE_BALANCE.remove(deps.storage);
+
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address, &config.denom)?;
+    let amount = balance.amount.checked_sub(before).map_err(StdError::from)?;
+
+    let mut resp = Response::new().add_attribute("cascaded", amount.to_string());
+    if !amount.is_zero() {
+        let distribute_msg = WasmMsg::Execute {
+            contract_addr: config.distribution_contract.into_string(),
+            msg: to_json_binary(&DistribtionExecMsg::Distribute {})?,
+            funds: coins(amount.u128(), &config.denom),
+        };
+        resp = resp.add_message(distribute_msg);
+    }
+
     Ok(resp)
 }
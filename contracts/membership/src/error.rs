@@ -1,4 +1,6 @@
+use common::validate::ValidationError;
 use cosmwasm_std::StdError;
+use cw_controllers::HookError;
 use cw_utils::ParseReplyError;
 use thiserror::Error;
 
@@ -8,6 +10,12 @@ pub enum ContractError {
     Std(#[from] StdError),
     #[error("{0}")]
     ParseReplyError(#[from] ParseReplyError),
+    #[error("{0}")]
+    Hook(#[from] HookError),
+    #[error("{0}")]
+    Validation(#[from] ValidationError),
+    #[error("Initial member {0} is listed more than once")]
+    DuplicateMember(String),
     #[error("Not enough initial members")]
     NotEnoughInitialMembers,
     #[error("Not enough required acceptances")]
@@ -22,4 +30,30 @@ pub enum ContractError {
     AlreadyVoted,
     #[error("Cannot propose a member")]
     AlreadyAMember,
+    #[error("Paused")]
+    Paused,
+    #[error("System is not paused")]
+    NotPaused,
+    #[error("Not a member")]
+    NotAMember,
+    #[error("Group is full")]
+    GroupFull,
+    #[error("Candidate is already on the waitlist")]
+    AlreadyWaitlisted,
+    #[error("Invalid treasury part")]
+    InvalidTreasuryPart,
+    #[error("Swap max spread has to be between 0 and 1")]
+    InvalidMaxSpread,
+    #[error("Spend proposal {0} is already executed")]
+    SpendExecuted(u64),
+    #[error("Spend proposal {0} expired")]
+    SpendExpired(u64),
+    #[error("Treasury can not execute on {0}")]
+    SpendTargetNotAllowed(String),
+    #[error("Spend voting period has to be positive")]
+    InvalidSpendVotingPeriod,
+    #[error("Group is already a member of a parent group")]
+    AlreadyNested,
+    #[error("Group is not a member of a parent group")]
+    NotNested,
 }
@@ -1,6 +1,9 @@
-use common::msg::ProposeMemberData;
+use crate::state::SpendStatus;
+use common::msg::{IbcDenom, PausableAction, PauseState, ProposeMemberData, SpendMsg, SwapConfig};
+use common::validate::{self, Validate, ValidationError};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Decimal;
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use cw4::{HooksResponse, MemberListResponse, MemberResponse, TotalWeightResponse};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -12,13 +15,209 @@ pub struct InstantiateMsg {
     pub distribution_code_id: u64,
     pub minimal_acceptance: u64,
     pub initial_members: Vec<String>, // vec contains initial members (content creators) addresses
+    pub guardian: Option<String>,     // can pause the system in emergency
+    pub max_members: Option<u64>,     // None => no limit
+    pub badge: Option<BadgeConfig>,   // None => members get no badges
+    pub receipts: Option<ReceiptsConfig>, // None => donors get no receipts
+    pub treasury_part: Option<Decimal>, // slice of distributed donations kept by the group
+    pub ibc_denoms: Option<Vec<IbcDenom>>, // accepted by proxies next to denom
+    pub swap: Option<SwapConfig>,     // None => proxies accept no other tokens
+    pub spend_voting_period: Option<u64>, // None => spend proposals stay open for a week
+}
+
+impl Validate for InstantiateMsg {
+    fn validate(&self) -> Result<(), ValidationError> {
+        validate::denom(&self.denom)?;
+        validate::direct_part(self.direct_part)?;
+        validate::halftime(self.halftime)?;
+        validate::starting_weight(self.starting_weight)?;
+        validate::unique_members(&self.initial_members)
+    }
+}
+
+impl InstantiateMsg {
+    // optional parts are off by default
+    pub fn builder(
+        denom: &str,
+        proxy_code_id: u64,
+        distribution_code_id: u64,
+    ) -> InstantiateMsgBuilder {
+        InstantiateMsgBuilder(InstantiateMsg {
+            starting_weight: 10,
+            denom: denom.to_owned(),
+            direct_part: Decimal::percent(15),
+            halftime: 3600 * 24 * 30,
+            proxy_code_id,
+            distribution_code_id,
+            minimal_acceptance: 2,
+            initial_members: vec![],
+            guardian: None,
+            max_members: None,
+            badge: None,
+            receipts: None,
+            treasury_part: None,
+            ibc_denoms: None,
+            swap: None,
+            spend_voting_period: None,
+        })
+    }
+}
+
+pub struct InstantiateMsgBuilder(InstantiateMsg);
+
+impl InstantiateMsgBuilder {
+    pub fn starting_weight(mut self, starting_weight: u64) -> Self {
+        self.0.starting_weight = starting_weight;
+        self
+    }
+
+    pub fn direct_part(mut self, direct_part: Decimal) -> Self {
+        self.0.direct_part = direct_part;
+        self
+    }
+
+    pub fn halftime(mut self, halftime: u64) -> Self {
+        self.0.halftime = halftime;
+        self
+    }
+
+    pub fn minimal_acceptance(mut self, minimal_acceptance: u64) -> Self {
+        self.0.minimal_acceptance = minimal_acceptance;
+        self
+    }
+
+    pub fn initial_members(mut self, initial_members: &[&str]) -> Self {
+        self.0.initial_members = initial_members
+            .iter()
+            .map(|addr| addr.to_string())
+            .collect();
+        self
+    }
+
+    pub fn guardian(mut self, guardian: &str) -> Self {
+        self.0.guardian = Some(guardian.to_owned());
+        self
+    }
+
+    pub fn max_members(mut self, max_members: u64) -> Self {
+        self.0.max_members = Some(max_members);
+        self
+    }
+
+    pub fn badge(mut self, badge: BadgeConfig) -> Self {
+        self.0.badge = Some(badge);
+        self
+    }
+
+    pub fn receipts(mut self, receipts: ReceiptsConfig) -> Self {
+        self.0.receipts = Some(receipts);
+        self
+    }
+
+    pub fn treasury_part(mut self, treasury_part: Decimal) -> Self {
+        self.0.treasury_part = Some(treasury_part);
+        self
+    }
+
+    pub fn ibc_denoms(mut self, ibc_denoms: Vec<IbcDenom>) -> Self {
+        self.0.ibc_denoms = Some(ibc_denoms);
+        self
+    }
+
+    pub fn swap(mut self, swap: SwapConfig) -> Self {
+        self.0.swap = Some(swap);
+        self
+    }
+
+    pub fn spend_voting_period(mut self, spend_voting_period: u64) -> Self {
+        self.0.spend_voting_period = Some(spend_voting_period);
+        self
+    }
+
+    pub fn build(self) -> Result<InstantiateMsg, ValidationError> {
+        self.0.validate()?;
+        Ok(self.0)
+    }
+}
+
+#[cw_serde]
+pub struct BadgeConfig {
+    pub code_id: u64,
+    pub transferable: bool,
+}
+
+#[cw_serde]
+pub struct ReceiptsConfig {
+    pub code_id: u64,
+    pub threshold: Uint128, // minimal donation getting a receipt
 }
 
 #[cw_serde]
 pub enum ExecMsg {
-    ProposeMember { addr: String },
+    ProposeMember {
+        addr: String,
+    },
+    // sent by a proxy when its ownership was accepted by a new owner
+    OwnershipTransferred {
+        previous_owner: String,
+        new_owner: String,
+    },
+    // guardian only, None pauses everything
+    Pause {
+        actions: Option<Vec<PausableAction>>,
+    },
+    // sent by member proxies, unpauses after minimal acceptances
+    ProposeUnpause {},
+    // member is the owner address, removed after minimal acceptances
+    ProposeRemoval {
+        member: String,
+        reason: String,
+    },
+    // sent by a member proxy whose owner leaves the group
+    Leave {},
+    // sent by a member proxy whenever its weight changes
+    WeightChanged {
+        weight: u64,
+    },
+    // sent by a member proxy of this group for every donation it splits
+    Donated {
+        amount: Coin,
+    },
+    // sent by a member proxy of this group when its update_weight was performed
+    Decayed {},
+    // cw4 hooks, guardian only
+    AddHook {
+        addr: String,
+    },
+    RemoveHook {
+        addr: String,
+    },
+    // sent by member proxies, treasury executes msgs after minimal acceptances
+    ProposeSpend {
+        msgs: Vec<SpendMsg>,
+        description: String,
+    },
+    VoteSpend {
+        id: u64,
+    },
+    // guardian only, the parent group still has to vote this group in
+    JoinParent {
+        parent: String,
+    },
+    // sent by the parent group once this group was accepted
+    ParentAccepted {
+        distribution_contract: String,
+    },
+    // sent by the parent group when this group was voted out
+    ParentRemoved {},
+    // anyone, passes the share earned in the parent group down to this group's distribution
+    Cascade {},
 }
 
+// nothing to convert so far, migrating only replaces the code
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub struct InstantiationData {
     pub members: Vec<ProposeMemberData>,
@@ -29,6 +228,100 @@ pub struct InstantiationData {
 pub enum QueryMsg {
     #[returns(IsMemberResponse)]
     IsMember { addr: String },
+    #[returns(PauseState)]
+    Paused {},
+    #[returns(GroupInfoResponse)]
+    GroupInfo {},
+    #[returns(WaitlistResponse)]
+    Waitlist {},
+    // cw4 interface, at_height gives the weights as they were at the beginning of that block
+    #[returns(MemberResponse)]
+    Member {
+        addr: String,
+        at_height: Option<u64>,
+    },
+    #[returns(MemberListResponse)]
+    ListMembers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(TotalWeightResponse)]
+    TotalWeight { at_height: Option<u64> },
+    #[returns(HooksResponse)]
+    Hooks {},
+    #[returns(SpendProposalResponse)]
+    SpendProposal { id: u64 },
+    #[returns(ListSpendProposalsResponse)]
+    ListSpendProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // overview for dashboards, aggregated over the member proxies
+    #[returns(GroupStatsResponse)]
+    GroupStats {},
+    #[returns(MemberDetailsResponse)]
+    MemberDetails { owner: String },
+}
+
+#[cw_serde]
+pub struct GroupStatsResponse {
+    pub member_count: u64,
+    pub total_weight: u64,
+    // donated to the group's proxies in the group and IBC denoms, swapped ones in the group denom
+    pub total_donated: Vec<Coin>,
+    pub distribution_balance: Vec<Coin>,
+    pub pending_additions: u64, // candidates with some votes
+    pub pending_removals: u64,  // members with some votes to remove them
+    pub open_spend_proposals: u64,
+    pub last_decay: Option<u64>, // latest performed update_weight among the members
+}
+
+#[cw_serde]
+pub struct MemberDetailsResponse {
+    pub proxy: String,
+    pub weight: u64,
+    // proxy state, None for a member group
+    pub donations: Option<u64>, // counted since the last weight reset
+    pub last_updated: Option<u64>,
+    pub is_closed: bool,
+    pub pending_rewards: Uint128, // not yet withdrawn from the distribution contract
+}
+
+#[cw_serde]
+pub struct SpendProposalResponse {
+    pub id: u64,
+    pub description: String,
+    pub msgs: Vec<SpendMsg>,
+    pub votes: u64,
+    pub status: SpendStatus,
+    pub expires: u64,
+}
+
+#[cw_serde]
+pub struct ListSpendProposalsResponse {
+    pub proposals: Vec<SpendProposalResponse>,
+}
+
+#[cw_serde]
+pub struct GroupInfoResponse {
+    pub member_count: u64,
+    pub max_members: Option<u64>,
+    pub minimal_acceptances: u64,
+    pub badge_contract: Option<String>,
+    pub receipt_contract: Option<String>,
+    pub distribution_contract: String,
+    pub parent: Option<String>, // set as soon as the group asks to join, before it is accepted
+}
+
+#[cw_serde]
+pub struct WaitlistEntry {
+    pub addr: String,
+    pub position: u64, // starts from 1
+}
+
+#[cw_serde]
+pub struct WaitlistResponse {
+    pub candidates: Vec<WaitlistEntry>,
 }
 
 #[cw_serde]
@@ -1,15 +1,21 @@
 use anyhow::Result as AnyResult;
-use common::msg::ProposeMemberData;
+use common::msg::{IbcDenom, PausableAction, PauseState, ProposeMemberData, SwapConfig};
 use cosmwasm_std::{from_json, to_json_binary, Addr, Decimal, WasmMsg};
+use cw4::{MemberListResponse, MemberResponse, TotalWeightResponse};
 use cw_multi_test::{App, ContractWrapper, Executor};
 use cw_utils::{parse_execute_response_data, parse_instantiate_response_data};
 
 use crate::{
     execute, instantiate,
-    msg::{ExecMsg, InstantiateMsg, InstantiationData, IsMemberResponse, QueryMsg},
+    msg::{
+        BadgeConfig, ExecMsg, GroupInfoResponse, GroupStatsResponse, InstantiateMsg,
+        InstantiationData, IsMemberResponse, MemberDetailsResponse, QueryMsg, ReceiptsConfig,
+        SpendProposalResponse, WaitlistResponse,
+    },
     query, reply,
 };
 
+#[cfg(test)]
 mod test;
 
 #[derive(Clone, Copy, Debug)]
@@ -22,6 +28,7 @@ impl CodeId {
     }
 
     #[track_caller]
+    #[allow(clippy::too_many_arguments)]
     pub fn instantiate(
         self,
         app: &mut App,
@@ -32,8 +39,15 @@ impl CodeId {
         halftime: u64,
         minimal_acceptance: u64,
         proxy_code_id: proxy::multitest::CodeId,
-        //distribution_code_id: distribution::multitest::CodeId,
+        distribution_code_id: u64, // there is no distribution crate yet, see proxy::multitest::store_distribution_mock
         initial_members: &[&str],
+        guardian: Option<&str>,
+        max_members: Option<u64>,
+        badge: Option<BadgeConfig>,
+        receipts: Option<ReceiptsConfig>,
+        treasury_part: Option<Decimal>,
+        ibc_denoms: Option<Vec<IbcDenom>>,
+        swap: Option<SwapConfig>,
         label: &str,
     ) -> AnyResult<(Contract, InstantiationData)> {
         Contract::instantiate(
@@ -46,10 +60,29 @@ impl CodeId {
             halftime,
             minimal_acceptance,
             proxy_code_id,
+            distribution_code_id,
             initial_members,
+            guardian,
+            max_members,
+            badge,
+            receipts,
+            treasury_part,
+            ibc_denoms,
+            swap,
             label,
         )
     }
+
+    #[track_caller]
+    pub fn instantiate_with(
+        self,
+        app: &mut App,
+        sender: &str,
+        msg: InstantiateMsg,
+        label: &str,
+    ) -> AnyResult<(Contract, InstantiationData)> {
+        Contract::instantiate_with(app, self, sender, msg, label)
+    }
 }
 
 impl From<CodeId> for u64 {
@@ -67,6 +100,7 @@ impl Contract {
     }
 
     #[track_caller]
+    #[allow(clippy::too_many_arguments)]
     pub fn instantiate(
         app: &mut App,
         code_id: CodeId,
@@ -78,8 +112,15 @@ impl Contract {
         halftime: u64,
         minimal_acceptance: u64,
         proxy_code_id: proxy::multitest::CodeId,
-        //distribution_code_id: distribution::multitest::CodeId,
+        distribution_code_id: u64, // there is no distribution crate yet, see proxy::multitest::store_distribution_mock
         initial_members: &[&str],
+        guardian: Option<&str>,
+        max_members: Option<u64>,
+        badge: Option<BadgeConfig>,
+        receipts: Option<ReceiptsConfig>,
+        treasury_part: Option<Decimal>,
+        ibc_denoms: Option<Vec<IbcDenom>>,
+        swap: Option<SwapConfig>,
         label: &str,
     ) -> AnyResult<(Self, InstantiationData)> {
         let init_msg = InstantiateMsg {
@@ -88,19 +129,39 @@ impl Contract {
             direct_part,
             halftime,
             proxy_code_id: proxy_code_id.into(), // can use this because From<CodeId> for u64
-            distribution_code_id: 0,             // cause we don't have distribution contract yet
+            distribution_code_id,
             minimal_acceptance,
             initial_members: initial_members // need to find out this
                 .iter()
                 .map(|addr| addr.to_string())
                 .collect(),
+            guardian: guardian.map(str::to_owned),
+            max_members,
+            badge,
+            receipts,
+            treasury_part,
+            ibc_denoms,
+            swap,
+            spend_voting_period: None,
         };
 
+        Self::instantiate_with(app, code_id, sender, init_msg, label)
+    }
+
+    // msg usually comes from InstantiateMsg::builder
+    #[track_caller]
+    pub fn instantiate_with(
+        app: &mut App,
+        code_id: CodeId,
+        sender: &str,
+        msg: InstantiateMsg,
+        label: &str,
+    ) -> AnyResult<(Self, InstantiationData)> {
         // instantiate membership contract
         let init_msg = WasmMsg::Instantiate {
             admin: None,
             code_id: code_id.0,
-            msg: to_json_binary(&init_msg)?,
+            msg: to_json_binary(&msg)?,
             funds: vec![],
             label: label.into(),
         };
@@ -112,7 +173,7 @@ impl Contract {
         let data = parse_instantiate_response_data(resp.data.unwrap_or_default().as_slice())?;
 
         let contract = Self(Addr::unchecked(data.contract_address));
-        let data = from_json(&data.data.unwrap_or_default())?;
+        let data = from_json(data.data.unwrap_or_default())?;
 
         Ok((contract, data))
     }
@@ -137,7 +198,7 @@ impl Contract {
         // this is my code
         let data = parse_execute_response_data(resp.data.unwrap_or_default().as_slice())?;
 
-        let data = from_json(&data.data.unwrap_or_default())?;
+        let data = from_json(data.data.unwrap_or_default())?;
 
         // this is code from repo
         // resp.data
@@ -153,6 +214,128 @@ impl Contract {
         Ok(data)
     }
 
+    #[track_caller]
+    pub fn pause(
+        &self,
+        app: &mut App,
+        sender: &str,
+        actions: Option<Vec<PausableAction>>,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::Pause { actions };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    pub fn paused(&self, app: &App) -> AnyResult<PauseState> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Paused {})?;
+        Ok(resp)
+    }
+
+    pub fn group_info(&self, app: &App) -> AnyResult<GroupInfoResponse> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::GroupInfo {})?;
+        Ok(resp)
+    }
+
+    pub fn group_stats(&self, app: &App) -> AnyResult<GroupStatsResponse> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::GroupStats {})?;
+        Ok(resp)
+    }
+
+    pub fn member_details(&self, app: &App, owner: &str) -> AnyResult<MemberDetailsResponse> {
+        let msg = QueryMsg::MemberDetails {
+            owner: owner.to_owned(),
+        };
+        let resp = app.wrap().query_wasm_smart(self.0.clone(), &msg)?;
+        Ok(resp)
+    }
+
+    pub fn waitlist(&self, app: &App) -> AnyResult<WaitlistResponse> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Waitlist {})?;
+        Ok(resp)
+    }
+
+    #[track_caller]
+    pub fn add_hook(&self, app: &mut App, sender: &str, addr: &str) -> AnyResult<()> {
+        let msg = ExecMsg::AddHook {
+            addr: addr.to_owned(),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    pub fn member(&self, app: &App, addr: &str) -> AnyResult<MemberResponse> {
+        let resp = app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Member {
+                addr: addr.to_owned(),
+                at_height: None,
+            },
+        )?;
+        Ok(resp)
+    }
+
+    pub fn list_members(
+        &self,
+        app: &App,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> AnyResult<MemberListResponse> {
+        let resp = app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::ListMembers {
+                start_after: start_after.map(str::to_owned),
+                limit,
+            },
+        )?;
+        Ok(resp)
+    }
+
+    pub fn total_weight(&self, app: &App) -> AnyResult<TotalWeightResponse> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::TotalWeight { at_height: None })?;
+        Ok(resp)
+    }
+
+    #[track_caller]
+    pub fn join_parent(&self, app: &mut App, sender: &str, parent: &str) -> AnyResult<()> {
+        let msg = ExecMsg::JoinParent {
+            parent: parent.to_owned(),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn cascade(&self, app: &mut App, sender: &str) -> AnyResult<()> {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            self.0.clone(),
+            &ExecMsg::Cascade {},
+            &[],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn spend_proposal(&self, app: &App, id: u64) -> AnyResult<SpendProposalResponse> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::SpendProposal { id })?;
+        Ok(resp)
+    }
+
     pub fn is_member(&self, app: &App, addr: &str) -> AnyResult<IsMemberResponse> {
         // this is my code different from repo
         let resp = app.wrap().query_wasm_smart(
@@ -1,11 +1,33 @@
 use std::collections::HashMap;
 
-use cosmwasm_std::Addr;
+use common::msg::{
+    DistributionInstantiateMsg, DistributionQueryMsg, PausableAction, PauseState,
+    PendingRewardsResponse, SpendMsg,
+};
+use common::validate::ValidationError;
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Decimal;
-use cw_multi_test::App;
+use cosmwasm_std::{coin, coins, Addr, BankMsg, Uint128};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+};
+use cw4::{Member, MemberChangedHookMsg, MemberDiff, MemberResponse, TotalWeightResponse};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_storage_plus::Item;
 
-use crate::multitest::CodeId as MembershipId;
-use proxy::multitest::CodeId as ProxyId;
+use crate::error::ContractError;
+use crate::msg::{BadgeConfig, ExecMsg, InstantiateMsg, QueryMsg, ReceiptsConfig, WaitlistEntry};
+use crate::state::SpendStatus;
+use badge::error::ContractError as BadgeError;
+use badge::multitest::{CodeId as BadgeId, Contract as BadgeContract};
+use proxy::error::ContractError as ProxyError;
+use proxy::msg::{DistribtionExecMsg, ExecMsg as ProxyExecMsg};
+use receipt::error::ContractError as ReceiptError;
+use receipt::msg::{ExecMsg as ReceiptExecMsg, ReceiptMetadata};
+use receipt::multitest::{CodeId as ReceiptId, Contract as ReceiptContract};
+
+use crate::multitest::{CodeId as MembershipId, Contract as MembershipContract};
+use proxy::multitest::{store_distribution_mock, CodeId as ProxyId};
 
 use proxy::multitest::Contract as ProxyContract;
 
@@ -21,6 +43,7 @@ pub fn adding_member() {
 
     // deploy code to blockchain => get code_id
     let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
     let membership_code_id = MembershipId::store_code(&mut app);
 
     // the reason to have the contract instantiate fn in the CodeId Wrapper
@@ -35,7 +58,15 @@ pub fn adding_member() {
             3600 * 24 * 30, // 30 days => update weight
             2,
             proxy_code_id,
+            distribution_code_id,
             &initial_members,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             "Membership",
         )
         .unwrap(); // so remember if can not use ? operator, use unwrap() instead
@@ -89,3 +120,1990 @@ pub fn adding_member() {
             .is_member
     );
 }
+
+#[test]
+pub fn transferring_ownership() {
+    let mut app = App::default();
+
+    let initial_members = ["member1", "member2"];
+    let new_owner = "new_owner";
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            "ORAI",
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let proxies: HashMap<_, _> = instantiation_data
+        .members
+        .into_iter()
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+    let proxy = &proxies[initial_members[0]];
+
+    // member2 already has his own proxy in the group
+    proxy
+        .transfer_ownership(&mut app, initial_members[0], initial_members[1], None)
+        .unwrap();
+    proxy
+        .accept_ownership(&mut app, initial_members[1])
+        .unwrap_err();
+
+    // only the pending owner can accept
+    proxy
+        .transfer_ownership(&mut app, initial_members[0], new_owner, None)
+        .unwrap();
+    proxy
+        .accept_ownership(&mut app, initial_members[1])
+        .unwrap_err();
+    proxy.accept_ownership(&mut app, new_owner).unwrap();
+
+    let owner = proxy::state::OWNER
+        .query(&app.wrap(), proxy.addr().clone())
+        .unwrap();
+    assert_eq!(owner, new_owner);
+
+    // previous owner lost control over the proxy
+    proxy
+        .propose_member(&mut app, initial_members[0], "candidate")
+        .unwrap_err();
+
+    // new owner is a member now, previous one can be proposed again
+    proxies[initial_members[1]]
+        .propose_member(&mut app, initial_members[1], new_owner)
+        .unwrap_err();
+    proxies[initial_members[1]]
+        .propose_member(&mut app, initial_members[1], initial_members[0])
+        .unwrap();
+    let data = proxy
+        .propose_member(&mut app, new_owner, initial_members[0])
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.owner_addr, initial_members[0]);
+    assert!(
+        membership_contract
+            .is_member(&app, data.proxy_addr.as_str())
+            .unwrap()
+            .is_member
+    );
+}
+
+#[test]
+pub fn ownership_transfer_expiry() {
+    let mut app = App::default();
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let (_, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            "ORAI",
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &["member1", "member2"],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let proxy = instantiation_data
+        .members
+        .into_iter()
+        .find(|member| member.owner_addr == "member1")
+        .map(|member| ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)))
+        .unwrap();
+
+    let now = app.block_info().time.seconds();
+    proxy
+        .transfer_ownership(&mut app, "member1", "new_owner", Some(now))
+        .unwrap_err();
+    proxy
+        .transfer_ownership(&mut app, "member1", "new_owner", Some(now + 60))
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(60));
+    proxy.accept_ownership(&mut app, "new_owner").unwrap_err();
+}
+
+#[test]
+pub fn pausing_system() {
+    let mut app = App::default();
+
+    let initial_members = ["member1", "member2", "member3"];
+    let guardian = "guardian";
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("donor"), coins(100, "ORAI"))
+            .unwrap()
+    });
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            "ORAI",
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            Some(guardian),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let proxies: HashMap<_, _> = instantiation_data
+        .members
+        .into_iter()
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+    let proxy = &proxies[initial_members[0]];
+
+    // failed campaign to reclaim from and one still to be closed, both past the deadline
+    let deadline = app.block_info().time.seconds() + 3600;
+    for (id, title) in ["Tour", "Album"].into_iter().enumerate() {
+        proxy
+            .create_campaign(&mut app, initial_members[0], title, 500, deadline)
+            .unwrap();
+        proxy
+            .donate_to_campaign(&mut app, "donor", id as u64, &coins(40, "ORAI"))
+            .unwrap();
+    }
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    proxy.close_campaign(&mut app, "anyone", 0).unwrap();
+
+    // only guardian can pause
+    let err = membership_contract
+        .pause(&mut app, initial_members[0], None)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Unauthorized
+    ));
+
+    // pausing single action leaves the others working
+    membership_contract
+        .pause(&mut app, guardian, Some(vec![PausableAction::Donate]))
+        .unwrap();
+    let err = proxy.donate(&mut app, "donor", &[]).unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    proxy
+        .propose_member(&mut app, initial_members[0], "candidate")
+        .unwrap();
+
+    // closing a campaign and rolling a pledge over are donations as well
+    let err = proxy.close_campaign(&mut app, "anyone", 1).unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    let err = proxy
+        .reclaim_pledge(&mut app, "donor", 0, true)
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+
+    membership_contract.pause(&mut app, guardian, None).unwrap();
+    assert!(membership_contract.paused(&app).unwrap().all);
+
+    let err = proxy.donate(&mut app, "donor", &[]).unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    let err = proxy
+        .withdraw(&mut app, initial_members[0], None, None)
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    let err = proxy
+        .propose_member(&mut app, initial_members[0], "candidate")
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    let err = proxy.update_weight(&mut app, "anyone").unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    let err = proxy
+        .reclaim_pledge(&mut app, "donor", 0, false)
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    let err = proxy
+        .execute_withdrawal(&mut app, initial_members[0], 0)
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    let err = proxy
+        .propose_removal(&mut app, initial_members[0], initial_members[1], "inactive")
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    let err = proxy
+        .propose_spend(&mut app, initial_members[0], vec![], "tour bus")
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    let err = proxy
+        .vote_spend(&mut app, initial_members[0], 0)
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+
+    // membership checks proposals on its own as well
+    let err = membership_contract
+        .propose_member(&mut app, proxy.addr().as_str(), "candidate")
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ContractError::Paused));
+
+    // unpausing needs minimal acceptances of members
+    proxy.vote_unpause(&mut app, initial_members[0]).unwrap();
+    let err = proxy
+        .vote_unpause(&mut app, initial_members[0])
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::AlreadyVoted
+    ));
+    assert!(membership_contract.paused(&app).unwrap().all);
+
+    proxies[initial_members[1]]
+        .vote_unpause(&mut app, initial_members[1])
+        .unwrap();
+    assert_eq!(
+        membership_contract.paused(&app).unwrap(),
+        PauseState::default()
+    );
+
+    // auto forward is a withdrawal, so donations to a forwarding proxy stop with withdrawals
+    membership_contract
+        .pause(&mut app, guardian, Some(vec![PausableAction::Withdraw]))
+        .unwrap();
+    proxy
+        .set_auto_forward(&mut app, initial_members[0], Some("cold_wallet"))
+        .unwrap();
+    let err = proxy
+        .donate(&mut app, "donor", &coins(20, "ORAI"))
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+    assert_eq!(
+        app.wrap()
+            .query_balance("cold_wallet", "ORAI")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+    proxy
+        .set_auto_forward(&mut app, initial_members[0], None)
+        .unwrap();
+    proxy.donate(&mut app, "donor", &coins(20, "ORAI")).unwrap();
+
+    // proxy created during a pause gets the pause state too
+    membership_contract
+        .pause(&mut app, guardian, Some(vec![PausableAction::Donate]))
+        .unwrap();
+    let data = proxies[initial_members[1]]
+        .propose_member(&mut app, initial_members[1], "candidate")
+        .unwrap()
+        .unwrap();
+    let new_proxy = ProxyContract::from_addr(Addr::unchecked(data.proxy_addr));
+    let err = new_proxy.donate(&mut app, "donor", &[]).unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Paused));
+}
+
+#[test]
+pub fn removing_member() {
+    let mut app = App::default();
+
+    let denom = "ORAI";
+    let initial_members = ["member1", "member2", "member3"];
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("donor"), coins(100, denom))
+            .unwrap()
+    });
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            denom,
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let proxies: HashMap<_, _> = instantiation_data
+        .members
+        .into_iter()
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+    let removed = &proxies[initial_members[2]];
+
+    // direct part stays on the proxy
+    removed
+        .donate(&mut app, "donor", &coins(100, denom))
+        .unwrap();
+
+    // member can not vote on own removal
+    let err = removed
+        .propose_removal(&mut app, initial_members[2], initial_members[2], "spam")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Unauthorized
+    ));
+
+    let err = proxies[initial_members[0]]
+        .propose_removal(&mut app, initial_members[0], "stranger", "spam")
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ContractError::NotAMember));
+
+    proxies[initial_members[0]]
+        .propose_removal(&mut app, initial_members[0], initial_members[2], "spam")
+        .unwrap();
+    let err = proxies[initial_members[0]]
+        .propose_removal(&mut app, initial_members[0], initial_members[2], "spam")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::AlreadyVoted
+    ));
+    assert!(
+        membership_contract
+            .is_member(&app, removed.addr().as_str())
+            .unwrap()
+            .is_member
+    );
+
+    proxies[initial_members[1]]
+        .propose_removal(&mut app, initial_members[1], initial_members[2], "spam")
+        .unwrap();
+    assert!(
+        !membership_contract
+            .is_member(&app, removed.addr().as_str())
+            .unwrap()
+            .is_member
+    );
+
+    // removed proxy takes no more donations, but its owner keeps what was earned
+    let err = removed.donate(&mut app, "donor", &[]).unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Closed));
+
+    removed
+        .withdraw(&mut app, initial_members[2], None, None)
+        .unwrap();
+    assert_eq!(
+        app.wrap()
+            .query_balance(initial_members[2], denom)
+            .unwrap()
+            .amount
+            .u128(),
+        15
+    );
+
+    // decay on a removed proxy is a no-op instead of a stats update membership refuses
+    app.update_block(|block| block.time = block.time.plus_seconds(3600 * 24 * 31));
+    let data = removed.update_weight(&mut app, "anyone").unwrap().unwrap();
+    assert!(!data.performed);
+    assert_eq!(data.new_weight, 0);
+
+    // removed member can be proposed again
+    proxies[initial_members[0]]
+        .propose_member(&mut app, initial_members[0], initial_members[2])
+        .unwrap();
+}
+
+#[test]
+pub fn instantiate_validation() {
+    let mut app = App::default();
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let builder = || {
+        InstantiateMsg::builder("ORAI", proxy_code_id.into(), distribution_code_id)
+            .initial_members(&["member1", "member2"])
+    };
+
+    let err = builder().halftime(0).build().unwrap_err();
+    assert_eq!(err, ValidationError::ZeroHalftime);
+    let err = builder().starting_weight(0).build().unwrap_err();
+    assert_eq!(err, ValidationError::ZeroStartingWeight);
+    let err = builder()
+        .direct_part(Decimal::percent(150))
+        .build()
+        .unwrap_err();
+    assert_eq!(err, ValidationError::InvalidDirectPart);
+    let err = builder()
+        .initial_members(&["member1", "member2", "member1"])
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ValidationError::DuplicatedInitialMember("member1".to_owned())
+    );
+    let err = builder()
+        .initial_members(&["member1", "member2", "MEMBER1"])
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ValidationError::DuplicatedInitialMember("MEMBER1".to_owned())
+    );
+
+    // contract checks the message on its own, before any proxy is spawned
+    let mut msg = builder().build().unwrap();
+    msg.denom = "O".to_owned();
+    let err = membership_code_id
+        .instantiate_with(&mut app, "owner", msg, "Membership")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Validation(ValidationError::InvalidDenom(_))
+    ));
+
+    membership_code_id
+        .instantiate_with(&mut app, "owner", builder().build().unwrap(), "Membership")
+        .unwrap();
+}
+
+#[test]
+pub fn duplicate_initial_members() {
+    let mut app = App::default();
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    // a single member listed twice would satisfy the two required acceptances on its own
+    for initial_members in [
+        &["member1", "member1"][..],
+        &["member1", "member2", "member1"][..],
+    ] {
+        let msg = InstantiateMsg {
+            initial_members: initial_members
+                .iter()
+                .map(|addr| addr.to_string())
+                .collect(),
+            ..InstantiateMsg::builder("ORAI", proxy_code_id.into(), distribution_code_id)
+                .build()
+                .unwrap()
+        };
+        let err = membership_code_id
+            .instantiate_with(&mut app, "owner", msg, "Membership")
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast().unwrap(),
+            ContractError::DuplicateMember(member) if member == "member1"
+        ));
+    }
+}
+
+#[test]
+pub fn group_size_limit() {
+    let mut app = App::default();
+
+    let initial_members = ["member1", "member2"];
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    // initial members have to fit into the group
+    let err = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            "ORAI",
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            None,
+            Some(1),
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ContractError::GroupFull));
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            "ORAI",
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            None,
+            Some(3),
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let info = membership_contract.group_info(&app).unwrap();
+    assert_eq!(info.member_count, 2);
+    assert_eq!(info.max_members, Some(3));
+
+    let proxies: Vec<_> = instantiation_data
+        .members
+        .into_iter()
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+
+    for (owner, proxy) in &proxies {
+        proxy.propose_member(&mut app, owner, "member3").unwrap();
+    }
+    assert_eq!(
+        membership_contract.group_info(&app).unwrap().member_count,
+        3
+    );
+
+    // full group does not admit anyone else right away
+    for (owner, proxy) in &proxies {
+        let data = proxy.propose_member(&mut app, owner, "member4").unwrap();
+        assert_eq!(data, None);
+    }
+    assert_eq!(
+        membership_contract.group_info(&app).unwrap().member_count,
+        3
+    );
+}
+
+#[test]
+pub fn waitlist() {
+    let mut app = App::default();
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            "ORAI",
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &["member1", "member2", "member3"],
+            None,
+            Some(3),
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let proxies: HashMap<_, _> = instantiation_data
+        .members
+        .into_iter()
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+    let voters = ["member1", "member2"];
+
+    // accepted candidates queue up in the order of acceptance
+    for candidate in ["member4", "member5", "member6"] {
+        for voter in voters {
+            let data = proxies[voter]
+                .propose_member(&mut app, voter, candidate)
+                .unwrap();
+            assert_eq!(data, None);
+        }
+    }
+    assert_eq!(
+        membership_contract.waitlist(&app).unwrap().candidates,
+        [
+            WaitlistEntry {
+                addr: "member4".to_owned(),
+                position: 1,
+            },
+            WaitlistEntry {
+                addr: "member5".to_owned(),
+                position: 2,
+            },
+            WaitlistEntry {
+                addr: "member6".to_owned(),
+                position: 3,
+            },
+        ]
+    );
+
+    let err = proxies["member1"]
+        .propose_member(&mut app, "member1", "member4")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::AlreadyWaitlisted
+    ));
+
+    // only the owner leaves through his proxy
+    proxies["member3"].leave(&mut app, "member1").unwrap_err();
+
+    // leaving member frees the place for the first candidate
+    proxies["member3"].leave(&mut app, "member3").unwrap();
+    assert!(
+        !membership_contract
+            .is_member(&app, proxies["member3"].addr().as_str())
+            .unwrap()
+            .is_member
+    );
+    let err = proxies["member3"]
+        .donate(&mut app, "member3", &[])
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ProxyError::Closed));
+
+    let admitted = membership_contract.member_details(&app, "member4").unwrap();
+    assert!(
+        membership_contract
+            .is_member(&app, &admitted.proxy)
+            .unwrap()
+            .is_member
+    );
+    assert_eq!(
+        membership_contract.group_info(&app).unwrap().member_count,
+        3
+    );
+
+    // removal admits the next one
+    let member4 = ProxyContract::from_addr(Addr::unchecked(admitted.proxy));
+    proxies["member1"]
+        .propose_removal(&mut app, "member1", "member2", "inactive")
+        .unwrap();
+    member4
+        .propose_removal(&mut app, "member4", "member2", "inactive")
+        .unwrap();
+    assert!(membership_contract.member_details(&app, "member2").is_err());
+    assert!(membership_contract.member_details(&app, "member5").is_ok());
+
+    let waitlist = membership_contract.waitlist(&app).unwrap().candidates;
+    assert_eq!(
+        waitlist,
+        [WaitlistEntry {
+            addr: "member6".to_owned(),
+            position: 1,
+        }]
+    );
+
+    let err = proxies["member1"]
+        .propose_member(&mut app, "member1", "member4")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::AlreadyAMember
+    ));
+}
+
+#[test]
+pub fn member_badges() {
+    let mut app = App::default();
+
+    let initial_members = ["member1", "member2", "member3"];
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let badge_code_id = BadgeId::store_code(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            "ORAI",
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            None,
+            None,
+            Some(BadgeConfig {
+                code_id: badge_code_id.into(),
+                transferable: false,
+            }),
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let badge_contract = membership_contract
+        .group_info(&app)
+        .unwrap()
+        .badge_contract
+        .unwrap();
+    let badge_contract = BadgeContract::from_addr(Addr::unchecked(badge_contract));
+    assert_eq!(badge_contract.num_tokens(&app).unwrap(), 3);
+
+    let proxies: HashMap<_, _> = instantiation_data
+        .members
+        .into_iter()
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+
+    // token id is the proxy address
+    let proxy = proxies[initial_members[0]].addr().as_str();
+    let owner = badge_contract.owner_of(&app, proxy).unwrap();
+    assert_eq!(owner.owner, initial_members[0]);
+    let info = badge_contract.nft_info(&app, proxy).unwrap();
+    assert_eq!(info.extension.proxy, proxy);
+    assert_eq!(info.extension.joined_at, app.block_info().time.seconds());
+
+    // soulbound
+    let err = badge_contract
+        .transfer(&mut app, initial_members[0], "someone", proxy)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        BadgeError::NotTransferable
+    ));
+
+    // new member gets a badge, removed one loses it
+    for member in &initial_members[..2] {
+        proxies[*member]
+            .propose_member(&mut app, member, "member4")
+            .unwrap();
+        proxies[*member]
+            .propose_removal(&mut app, member, initial_members[2], "inactive")
+            .unwrap();
+    }
+    assert_eq!(badge_contract.num_tokens(&app).unwrap(), 3);
+    badge_contract
+        .owner_of(&app, proxies[initial_members[2]].addr().as_str())
+        .unwrap_err();
+
+    // badge follows the proxy ownership
+    proxies[initial_members[0]]
+        .transfer_ownership(&mut app, initial_members[0], "new_owner", None)
+        .unwrap();
+    proxies[initial_members[0]]
+        .accept_ownership(&mut app, "new_owner")
+        .unwrap();
+    let owner = badge_contract.owner_of(&app, proxy).unwrap();
+    assert_eq!(owner.owner, "new_owner");
+}
+
+#[test]
+pub fn donor_receipts() {
+    let mut app = App::default();
+
+    let denom = "ORAI";
+    let initial_members = ["member1", "member2"];
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("donor"), coins(200, denom))
+            .unwrap()
+    });
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let receipt_code_id = ReceiptId::store_code(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            denom,
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            None,
+            None,
+            None,
+            Some(ReceiptsConfig {
+                code_id: receipt_code_id.into(),
+                threshold: Uint128::new(50),
+            }),
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let receipt_contract = membership_contract
+        .group_info(&app)
+        .unwrap()
+        .receipt_contract
+        .unwrap();
+    let receipt_contract = ReceiptContract::from_addr(Addr::unchecked(receipt_contract));
+
+    let member = &instantiation_data.members[0];
+    let proxy = ProxyContract::from_addr(Addr::unchecked(&member.proxy_addr));
+
+    proxy.donate(&mut app, "donor", &coins(100, denom)).unwrap();
+    // below threshold
+    proxy.donate(&mut app, "donor", &coins(10, denom)).unwrap();
+
+    let receipts = receipt_contract.tokens(&app, "donor").unwrap();
+    assert_eq!(receipts.len(), 1);
+    let receipt = receipt_contract.nft_info(&app, &receipts[0]).unwrap();
+    assert_eq!(
+        receipt.extension,
+        ReceiptMetadata {
+            creator: member.owner_addr.clone(),
+            amount: Uint128::new(100),
+            denom: denom.to_owned(),
+            donated_at: app.block_info().time.seconds(),
+        }
+    );
+
+    // only member proxies mint
+    let mint_msg = ReceiptExecMsg::Mint {
+        token_id: "fake".to_owned(),
+        owner: "donor".to_owned(),
+        token_uri: None,
+        extension: receipt.extension,
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked("donor"),
+            receipt_contract.addr().clone(),
+            &mint_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ReceiptError::Unauthorized
+    ));
+}
+
+#[cw_serde]
+enum HookExecMsg {
+    MemberChangedHook(MemberChangedHookMsg),
+}
+
+const RECORDED_DIFFS: Item<Vec<MemberDiff>> = Item::new("diffs");
+
+// cw4 hook consumer which remembers every diff it was sent
+fn store_hook_recorder(app: &mut App) -> u64 {
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: HookExecMsg,
+    ) -> StdResult<Response> {
+        let HookExecMsg::MemberChangedHook(msg) = msg;
+        let mut diffs = RECORDED_DIFFS.may_load(deps.storage)?.unwrap_or_default();
+        diffs.extend(msg.diffs);
+        RECORDED_DIFFS.save(deps.storage, &diffs)?;
+        Ok(Response::new())
+    }
+
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&RECORDED_DIFFS.may_load(deps.storage)?.unwrap_or_default())
+    }
+
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    app.store_code(Box::new(contract))
+}
+
+#[test]
+pub fn cw4_group() {
+    let mut app = App::default();
+
+    let initial_members = ["member1", "member2"];
+    let guardian = "guardian";
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+    let hook_code_id = store_hook_recorder(&mut app);
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            "ORAI",
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            Some(guardian),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let hook = app
+        .instantiate_contract(
+            hook_code_id,
+            Addr::unchecked("owner"),
+            &Empty {},
+            &[],
+            "Hook",
+            None,
+        )
+        .unwrap();
+    let recorded = |app: &App| -> Vec<MemberDiff> {
+        app.wrap()
+            .query_wasm_smart(hook.clone(), &Empty {})
+            .unwrap()
+    };
+
+    let err = membership_contract
+        .add_hook(&mut app, initial_members[0], hook.as_str())
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Unauthorized
+    ));
+    membership_contract
+        .add_hook(&mut app, guardian, hook.as_str())
+        .unwrap();
+
+    assert_eq!(
+        membership_contract
+            .list_members(&app, None, None)
+            .unwrap()
+            .members,
+        [
+            Member {
+                addr: "member1".to_owned(),
+                weight: 10,
+            },
+            Member {
+                addr: "member2".to_owned(),
+                weight: 10,
+            },
+        ]
+    );
+
+    let proxies: Vec<_> = instantiation_data
+        .members
+        .into_iter()
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+
+    for (owner, proxy) in &proxies {
+        proxy.propose_member(&mut app, owner, "member3").unwrap();
+    }
+    assert_eq!(recorded(&app), [MemberDiff::new("member3", None, Some(10))]);
+    assert_eq!(membership_contract.total_weight(&app).unwrap().weight, 30);
+
+    // proxy reports decayed weight
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(3600 * 24 * 30);
+        block.height += 1;
+    });
+    let height = app.block_info().height;
+    let (owner, proxy) = &proxies[0];
+    proxy.update_weight(&mut app, owner).unwrap();
+    assert_eq!(
+        membership_contract.member(&app, owner).unwrap().weight,
+        Some(5)
+    );
+    assert_eq!(
+        recorded(&app)[1],
+        MemberDiff::new("member1", Some(10), Some(5))
+    );
+
+    // weights from before the decay are still readable at its height
+    let member: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(
+            membership_contract.addr(),
+            &QueryMsg::Member {
+                addr: owner.clone(),
+                at_height: Some(height),
+            },
+        )
+        .unwrap();
+    assert_eq!(member.weight, Some(10));
+    let total: TotalWeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            membership_contract.addr(),
+            &QueryMsg::TotalWeight {
+                at_height: Some(height),
+            },
+        )
+        .unwrap();
+    assert_eq!(total.weight, 30);
+    assert_eq!(membership_contract.total_weight(&app).unwrap().weight, 25);
+
+    for (owner, proxy) in &proxies {
+        proxy
+            .propose_removal(&mut app, owner, "member3", "inactive")
+            .unwrap();
+    }
+    assert_eq!(
+        recorded(&app)[2],
+        MemberDiff::new("member3", Some(10), None)
+    );
+    assert_eq!(
+        membership_contract.member(&app, "member3").unwrap().weight,
+        None
+    );
+    assert_eq!(membership_contract.total_weight(&app).unwrap().weight, 15);
+}
+
+#[test]
+pub fn treasury_spending() {
+    let mut app = App::default();
+
+    let denom = "ORAI";
+    let initial_members = ["member1", "member2", "member3"];
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("donor"), coins(100, denom))
+            .unwrap()
+    });
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    // treasury part above 100% is rejected
+    let err = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            denom,
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(101)),
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::InvalidTreasuryPart
+    ));
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            denom,
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(50)),
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let proxies: HashMap<_, _> = instantiation_data
+        .members
+        .into_iter()
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+
+    // 15 goes directly to the member, half of the remaining 85 is kept by the group
+    proxies[initial_members[0]]
+        .donate(&mut app, "donor", &coins(100, denom))
+        .unwrap();
+    assert_eq!(
+        app.wrap()
+            .query_balance(membership_contract.addr(), denom)
+            .unwrap()
+            .amount
+            .u128(),
+        42
+    );
+
+    // treasury can not be pointed at the group's own contracts
+    let spend_msg = SpendMsg::WasmExecute {
+        contract_addr: proxies[initial_members[1]].addr().to_string(),
+        msg: to_json_binary(&ProxyExecMsg::Close {}).unwrap(),
+        funds: vec![],
+    };
+    let err = proxies[initial_members[0]]
+        .propose_spend(&mut app, initial_members[0], vec![spend_msg], "close")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::SpendTargetNotAllowed(target) if target == proxies[initial_members[1]].addr().as_str()
+    ));
+
+    let spend_msg = SpendMsg::BankSend {
+        to_address: "grantee".to_owned(),
+        amount: coins(30, denom),
+    };
+    proxies[initial_members[0]]
+        .propose_spend(&mut app, initial_members[0], vec![spend_msg], "grant")
+        .unwrap();
+
+    let proposal = membership_contract.spend_proposal(&app, 0).unwrap();
+    assert_eq!(proposal.votes, 1);
+    assert_eq!(proposal.status, SpendStatus::Open);
+
+    // proposer already voted
+    let err = proxies[initial_members[0]]
+        .vote_spend(&mut app, initial_members[0], 0)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::AlreadyVoted
+    ));
+
+    // only member proxies vote
+    let err = app
+        .execute_contract(
+            Addr::unchecked("stranger"),
+            membership_contract.addr().clone(),
+            &ExecMsg::VoteSpend { id: 0 },
+            &[],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Unauthorized
+    ));
+
+    proxies[initial_members[1]]
+        .vote_spend(&mut app, initial_members[1], 0)
+        .unwrap();
+
+    let proposal = membership_contract.spend_proposal(&app, 0).unwrap();
+    assert_eq!(proposal.votes, 2);
+    assert_eq!(proposal.status, SpendStatus::Executed);
+    assert_eq!(
+        app.wrap()
+            .query_balance("grantee", denom)
+            .unwrap()
+            .amount
+            .u128(),
+        30
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(membership_contract.addr(), denom)
+            .unwrap()
+            .amount
+            .u128(),
+        12
+    );
+
+    let err = proxies[initial_members[2]]
+        .vote_spend(&mut app, initial_members[2], 0)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::SpendExecuted(0)
+    ));
+
+    // vote of a removed member does not count anymore
+    let spend_msg = SpendMsg::BankSend {
+        to_address: "grantee".to_owned(),
+        amount: coins(5, denom),
+    };
+    proxies[initial_members[0]]
+        .propose_spend(&mut app, initial_members[0], vec![spend_msg], "bonus")
+        .unwrap();
+    for voter in &initial_members[1..] {
+        proxies[*voter]
+            .propose_removal(&mut app, voter, initial_members[0], "inactive")
+            .unwrap();
+    }
+    proxies[initial_members[1]]
+        .vote_spend(&mut app, initial_members[1], 1)
+        .unwrap();
+    let proposal = membership_contract.spend_proposal(&app, 1).unwrap();
+    assert_eq!(proposal.votes, 1);
+    assert_eq!(proposal.status, SpendStatus::Open);
+
+    proxies[initial_members[2]]
+        .vote_spend(&mut app, initial_members[2], 1)
+        .unwrap();
+    assert_eq!(
+        membership_contract.spend_proposal(&app, 1).unwrap().status,
+        SpendStatus::Executed
+    );
+
+    // proposals stay open for a week by default
+    let spend_msg = SpendMsg::BankSend {
+        to_address: "grantee".to_owned(),
+        amount: coins(5, denom),
+    };
+    proxies[initial_members[1]]
+        .propose_spend(&mut app, initial_members[1], vec![spend_msg], "late")
+        .unwrap();
+    let proposal = membership_contract.spend_proposal(&app, 2).unwrap();
+    assert_eq!(
+        proposal.expires,
+        app.block_info().time.seconds() + 3600 * 24 * 7
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600 * 24 * 7));
+    let err = proxies[initial_members[2]]
+        .vote_spend(&mut app, initial_members[2], 2)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::SpendExpired(2)
+    ));
+    assert_eq!(
+        membership_contract.spend_proposal(&app, 2).unwrap().status,
+        SpendStatus::Expired
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance("grantee", denom)
+            .unwrap()
+            .amount
+            .u128(),
+        35
+    );
+}
+
+// unlike proxy::multitest::store_distribution_mock it pays everything it holds to whoever withdraws
+fn store_paying_distribution(app: &mut App) -> u64 {
+    const DENOM: Item<String> = Item::new("denom");
+
+    fn execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: DistribtionExecMsg,
+    ) -> StdResult<Response> {
+        let DistribtionExecMsg::Withdraw { .. } = msg else {
+            return Ok(Response::new());
+        };
+
+        let balance = deps.querier.query_all_balances(env.contract.address)?;
+        if balance.is_empty() {
+            return Ok(Response::new());
+        }
+
+        let resp = Response::new().add_message(BankMsg::Send {
+            to_address: info.sender.into_string(),
+            amount: balance,
+        });
+        Ok(resp)
+    }
+
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: DistributionInstantiateMsg,
+    ) -> StdResult<Response> {
+        DENOM.save(deps.storage, &msg.denom)?;
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, env: Env, msg: DistributionQueryMsg) -> StdResult<Binary> {
+        match msg {
+            DistributionQueryMsg::PendingRewards { .. } => {
+                let denom = DENOM.load(deps.storage)?;
+                let balance = deps.querier.query_balance(env.contract.address, denom)?;
+                to_json_binary(&PendingRewardsResponse {
+                    amount: balance.amount,
+                })
+            }
+        }
+    }
+
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    app.store_code(Box::new(contract))
+}
+
+#[test]
+pub fn nested_groups() {
+    let mut app = App::default();
+
+    let denom = "ORAI";
+    let guardian = "guardian";
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("donor"), coins(100, denom))
+            .unwrap()
+    });
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_paying_distribution(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let (squad, squad_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            denom,
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &["squad1", "squad2"],
+            Some(guardian),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Squad",
+        )
+        .unwrap();
+
+    let (collective, collective_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            denom,
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &["member1", "member2"],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Collective",
+        )
+        .unwrap();
+
+    let proxies: HashMap<_, _> = collective_data
+        .members
+        .into_iter()
+        .chain(squad_data.members)
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+
+    // only the guardian decides where the group belongs
+    let err = squad
+        .join_parent(&mut app, "squad1", collective.addr().as_str())
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Unauthorized
+    ));
+
+    squad
+        .join_parent(&mut app, guardian, collective.addr().as_str())
+        .unwrap();
+
+    proxies["member1"]
+        .propose_member(&mut app, "member1", squad.addr().as_str())
+        .unwrap();
+    let data = proxies["member2"]
+        .propose_member(&mut app, "member2", squad.addr().as_str())
+        .unwrap()
+        .unwrap();
+
+    // the squad is its own proxy, weighted as all its members together
+    assert_eq!(data.owner_addr, squad.addr().as_str());
+    assert_eq!(data.proxy_addr, squad.addr().as_str());
+    assert!(
+        collective
+            .is_member(&app, squad.addr().as_str())
+            .unwrap()
+            .is_member
+    );
+    assert_eq!(
+        collective
+            .member(&app, squad.addr().as_str())
+            .unwrap()
+            .weight,
+        Some(20)
+    );
+    assert_eq!(collective.total_weight(&app).unwrap().weight, 40);
+
+    let err = squad
+        .join_parent(&mut app, guardian, "other_group")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::AlreadyNested
+    ));
+
+    // weight of the squad follows its members
+    proxies["squad1"]
+        .propose_member(&mut app, "squad1", "squad3")
+        .unwrap();
+    proxies["squad2"]
+        .propose_member(&mut app, "squad2", "squad3")
+        .unwrap();
+    assert_eq!(
+        collective
+            .member(&app, squad.addr().as_str())
+            .unwrap()
+            .weight,
+        Some(30)
+    );
+    assert_eq!(collective.total_weight(&app).unwrap().weight, 50);
+
+    // the squad has no proxy state of its own
+    let details = collective
+        .member_details(&app, squad.addr().as_str())
+        .unwrap();
+    assert_eq!(details.proxy, squad.addr().as_str());
+    assert_eq!(details.weight, 30);
+    assert_eq!(details.donations, None);
+    assert_eq!(details.last_updated, None);
+
+    // distributed part reaches the squad's distribution
+    proxies["member1"]
+        .donate(&mut app, "donor", &coins(100, denom))
+        .unwrap();
+    let details = collective
+        .member_details(&app, squad.addr().as_str())
+        .unwrap();
+    assert_eq!(details.pending_rewards.u128(), 85);
+    squad.cascade(&mut app, "anyone").unwrap();
+
+    let squad_distribution = squad.group_info(&app).unwrap().distribution_contract;
+    assert_eq!(
+        app.wrap()
+            .query_balance(squad_distribution, denom)
+            .unwrap()
+            .amount
+            .u128(),
+        85
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(squad.addr(), denom)
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+
+    // voted out squad keeps its members but leaves the collective
+    proxies["member1"]
+        .propose_removal(&mut app, "member1", squad.addr().as_str(), "inactive")
+        .unwrap();
+    proxies["member2"]
+        .propose_removal(&mut app, "member2", squad.addr().as_str(), "inactive")
+        .unwrap();
+
+    assert!(
+        !collective
+            .is_member(&app, squad.addr().as_str())
+            .unwrap()
+            .is_member
+    );
+    assert_eq!(collective.total_weight(&app).unwrap().weight, 20);
+    assert_eq!(squad.group_info(&app).unwrap().parent, None);
+    assert_eq!(squad.group_info(&app).unwrap().member_count, 3);
+
+    let err = squad.cascade(&mut app, "anyone").unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ContractError::NotNested));
+}
+
+#[test]
+pub fn federated_groups() {
+    let mut app = App::default();
+
+    let denom = "ORAI";
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("donor"), coins(100, denom))
+            .unwrap()
+    });
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_paying_distribution(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let mut groups = vec![];
+    for (initial_members, label) in [
+        (["member1", "member2"], "Home"),
+        (["member3", "member4"], "Other"),
+    ] {
+        groups.push(
+            membership_code_id
+                .instantiate(
+                    &mut app,
+                    "owner",
+                    10,
+                    denom,
+                    Decimal::percent(15),
+                    3600 * 24 * 30,
+                    2,
+                    proxy_code_id,
+                    distribution_code_id,
+                    &initial_members,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    label,
+                )
+                .unwrap(),
+        );
+    }
+    let (other, other_data) = groups.pop().unwrap();
+    let (home, home_data) = groups.pop().unwrap();
+
+    let proxies: HashMap<_, _> = home_data
+        .members
+        .into_iter()
+        .chain(other_data.members)
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+    let proxy = &proxies["member1"];
+
+    let err = proxy
+        .join_group(
+            &mut app,
+            "member1",
+            other.addr().as_str(),
+            Decimal::percent(101),
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ProxyError::InvalidGroupShare
+    ));
+
+    proxy
+        .join_group(
+            &mut app,
+            "member1",
+            other.addr().as_str(),
+            Decimal::percent(40),
+        )
+        .unwrap();
+
+    proxies["member3"]
+        .propose_member(&mut app, "member3", proxy.addr().as_str())
+        .unwrap();
+    let data = proxies["member4"]
+        .propose_member(&mut app, "member4", proxy.addr().as_str())
+        .unwrap()
+        .unwrap();
+
+    // the same proxy is a member of both groups
+    assert_eq!(data.owner_addr, "member1");
+    assert_eq!(data.proxy_addr, proxy.addr().as_str());
+    assert_eq!(other.member(&app, "member1").unwrap().weight, Some(10));
+    assert_eq!(other.group_info(&app).unwrap().member_count, 3);
+    let shares: Vec<_> = proxy
+        .groups(&app)
+        .unwrap()
+        .groups
+        .into_iter()
+        .map(|group| (group.membership, group.share))
+        .collect();
+    assert_eq!(
+        shares,
+        vec![
+            (home.addr().to_string(), Decimal::percent(60)),
+            (other.addr().to_string(), Decimal::percent(40)),
+        ]
+    );
+
+    // 85 is distributed, 40% of it goes to the other group
+    proxy.donate(&mut app, "donor", &coins(100, denom)).unwrap();
+    let distribution_balance = |app: &App, group: &MembershipContract| {
+        let distribution = group.group_info(app).unwrap().distribution_contract;
+        app.wrap()
+            .query_balance(distribution, denom)
+            .unwrap()
+            .amount
+            .u128()
+    };
+    assert_eq!(distribution_balance(&app, &home), 51);
+    assert_eq!(distribution_balance(&app, &other), 34);
+
+    // the donation counts in the home group only
+    assert_eq!(
+        home.group_stats(&app).unwrap().total_donated,
+        coins(100, denom)
+    );
+    assert_eq!(
+        other.group_stats(&app).unwrap().total_donated,
+        coins(0, denom)
+    );
+
+    // one withdrawal collects from both distributions
+    proxy.withdraw(&mut app, "member1", None, None).unwrap();
+    assert_eq!(
+        app.wrap()
+            .query_balance("member1", denom)
+            .unwrap()
+            .amount
+            .u128(),
+        100
+    );
+    assert_eq!(distribution_balance(&app, &home), 0);
+    assert_eq!(distribution_balance(&app, &other), 0);
+
+    // the other group can vote the proxy out, it stays in the home group
+    proxies["member3"]
+        .propose_removal(&mut app, "member3", "member1", "inactive")
+        .unwrap();
+    proxies["member4"]
+        .propose_removal(&mut app, "member4", "member1", "inactive")
+        .unwrap();
+
+    assert!(
+        !other
+            .is_member(&app, proxy.addr().as_str())
+            .unwrap()
+            .is_member
+    );
+    assert!(
+        home.is_member(&app, proxy.addr().as_str())
+            .unwrap()
+            .is_member
+    );
+    assert_eq!(proxy.groups(&app).unwrap().groups.len(), 1);
+}
+
+#[test]
+pub fn group_stats() {
+    let mut app = App::default();
+
+    let denom = "ORAI";
+    let initial_members = ["member1", "member2", "member3"];
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("donor"), coins(150, denom))
+            .unwrap()
+    });
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            denom,
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &initial_members,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    let proxies: HashMap<_, _> = instantiation_data
+        .members
+        .into_iter()
+        .map(|member| {
+            (
+                member.owner_addr,
+                ProxyContract::from_addr(Addr::unchecked(member.proxy_addr)),
+            )
+        })
+        .collect();
+
+    // donations are counted as the proxies report them
+    proxies[initial_members[0]]
+        .donate(&mut app, "donor", &coins(100, denom))
+        .unwrap();
+    proxies[initial_members[1]]
+        .donate(&mut app, "donor", &coins(50, denom))
+        .unwrap();
+    let stats = membership_contract.group_stats(&app).unwrap();
+    assert_eq!(stats.total_donated, coins(150, denom));
+    assert_eq!(stats.last_decay, None);
+
+    // only the group's own proxies report
+    app.execute_contract(
+        Addr::unchecked(initial_members[0]),
+        membership_contract.addr().clone(),
+        &ExecMsg::Donated {
+            amount: coin(100, denom),
+        },
+        &[],
+    )
+    .unwrap_err();
+    let stats = membership_contract.group_stats(&app).unwrap();
+    assert_eq!(stats.total_donated, coins(150, denom));
+
+    // additions and removals are pending until decided
+    proxies[initial_members[0]]
+        .propose_member(&mut app, initial_members[0], "candidate")
+        .unwrap();
+    proxies[initial_members[0]]
+        .propose_removal(&mut app, initial_members[0], initial_members[1], "spam")
+        .unwrap();
+    let stats = membership_contract.group_stats(&app).unwrap();
+    assert_eq!((stats.pending_additions, stats.pending_removals), (1, 1));
+
+    proxies[initial_members[2]]
+        .propose_member(&mut app, initial_members[2], "candidate")
+        .unwrap();
+    let stats = membership_contract.group_stats(&app).unwrap();
+    assert_eq!((stats.pending_additions, stats.pending_removals), (0, 1));
+
+    // leaving settles the removal as well
+    proxies[initial_members[1]]
+        .leave(&mut app, initial_members[1])
+        .unwrap();
+    let stats = membership_contract.group_stats(&app).unwrap();
+    assert_eq!((stats.pending_additions, stats.pending_removals), (0, 0));
+    assert_eq!(stats.member_count, 3);
+
+    // spend proposals are open until they expire
+    let spend_msg = SpendMsg::BankSend {
+        to_address: "grantee".to_owned(),
+        amount: coins(10, denom),
+    };
+    proxies[initial_members[0]]
+        .propose_spend(&mut app, initial_members[0], vec![spend_msg], "grant")
+        .unwrap();
+    let stats = membership_contract.group_stats(&app).unwrap();
+    assert_eq!(stats.open_spend_proposals, 1);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600 * 24 * 30));
+    proxies[initial_members[2]]
+        .update_weight(&mut app, initial_members[2])
+        .unwrap();
+    let decayed_at = app.block_info().time.seconds();
+    let stats = membership_contract.group_stats(&app).unwrap();
+    assert_eq!(stats.open_spend_proposals, 0);
+    assert_eq!(stats.last_decay, Some(decayed_at));
+}
+#[test]
+pub fn distribution_instantiation() {
+    let mut app = App::default();
+
+    let proxy_code_id = ProxyId::store_code(&mut app);
+    let distribution_code_id = store_distribution_mock(&mut app);
+    let membership_code_id = MembershipId::store_code(&mut app);
+
+    let (membership_contract, instantiation_data) = membership_code_id
+        .instantiate(
+            &mut app,
+            "owner",
+            10,
+            "ORAI",
+            Decimal::percent(15),
+            3600 * 24 * 30,
+            2,
+            proxy_code_id,
+            distribution_code_id,
+            &["member1", "member2"],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            "Membership",
+        )
+        .unwrap();
+
+    // distribution contract is created first and administrated by membership
+    let distribution = crate::state::CONFIG
+        .query(&app.wrap(), membership_contract.addr().clone())
+        .unwrap()
+        .distribution_contract;
+    let info = app.wrap().query_wasm_contract_info(&distribution).unwrap();
+    assert_eq!(info.code_id, distribution_code_id);
+    assert_eq!(info.admin, Some(membership_contract.addr().to_string()));
+
+    // initial proxies know it from their instantiation
+    assert_eq!(instantiation_data.members.len(), 2);
+    for member in instantiation_data.members {
+        let config = proxy::state::CONFIG
+            .query(&app.wrap(), Addr::unchecked(member.proxy_addr))
+            .unwrap();
+        assert_eq!(config.distribution_contract, distribution);
+    }
+}
@@ -1,6 +1,8 @@
+use common::msg::{IbcDenom, PauseState, SpendMsg, SwapConfig};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Empty};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
+use cw_controllers::Hooks;
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 
 #[cw_serde]
 pub struct Config {
@@ -11,14 +13,92 @@ pub struct Config {
     pub proxy_code_id: u64,
     pub distribution_contract: Addr,
     pub minimal_acceptances: u64,
+    pub guardian: Option<Addr>,
+    pub max_members: Option<u64>,
+    pub receipt_threshold: Option<Uint128>, // set only when the group has receipts
+    pub treasury_part: Decimal,
+    pub ibc_denoms: Vec<IbcDenom>,
+    pub swap: Option<SwapConfig>,
+    pub spend_voting_period: u64, // seconds a spend proposal stays open
+}
+
+#[cw_serde]
+pub enum SpendStatus {
+    Open,
+    Executed,
+    Expired, // only reported by queries, stored proposal stays open
+}
+
+#[cw_serde]
+pub struct SpendProposal {
+    pub description: String,
+    pub msgs: Vec<SpendMsg>,
+    pub votes: u64, // of proxies which are still members
+    pub status: SpendStatus,
+    pub expires: u64,
+}
+
+#[cw_serde]
+pub struct Parent {
+    pub membership: Addr,
+    pub distribution: Option<Addr>, // None until the parent group votes this one in
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const MEMBERS: Map<&Addr, Empty> = Map::new("members"); // proxy contracts addresses
+pub const MEMBER_COUNT: Item<u64> = Item::new("member_count"); // kept in sync with MEMBERS
+pub const WAITLIST: Item<Vec<Addr>> = Item::new("waitlist"); // accepted candidates, first in first out
+pub const PROXIES: Map<&Addr, Addr> = Map::new("proxies"); // owner => his proxy contract address
+
+// member groups, such a group is its own owner and proxy and reports its total weight
+pub const SUBGROUPS: Map<&Addr, Empty> = Map::new("subgroups");
+// proxies of other groups, paused and closed by their home group
+pub const FEDERATED_PROXIES: Map<&Addr, Empty> = Map::new("federated_proxies");
+pub const PARENT: Item<Parent> = Item::new("parent");
+// balance before pulling the share from the parent distribution, the rest is passed down
+pub const CASCADE_BALANCE: Item<Uint128> = Item::new("cascade_balance");
+
+// cw4 weights are snapshotted every block, so consumers can ask for them at a height
+// owner => weight of his proxy
+pub const WEIGHTS: SnapshotMap<&Addr, u64> = SnapshotMap::new(
+    "weights",
+    "weights__checkpoints",
+    "weights__changelog",
+    Strategy::EveryBlock,
+);
+pub const TOTAL_WEIGHT: SnapshotItem<u64> = SnapshotItem::new(
+    "total_weight",
+    "total_weight__checkpoints",
+    "total_weight__changelog",
+    Strategy::EveryBlock,
+);
+pub const HOOKS: Hooks = Hooks::new("cw4_hooks");
 pub const PROPOSALS: Map<&Addr, u64> = Map::new("proposals");
 // candidate want to join group, and number of their proposals
 
 pub const VOTES: Map<(&Addr, &Addr), Empty> = Map::new("votes");
 // member in system, candidate want to join group
 
+// proposals with some votes, kept in sync with PROPOSALS for the group stats
+pub const PENDING_ADDITIONS: Item<u64> = Item::new("pending_additions");
+pub const PENDING_REMOVALS: Item<u64> = Item::new("pending_removals");
+
+pub const PAUSED: Item<PauseState> = Item::new("paused");
+pub const UNPAUSE_VOTES: Map<&Addr, Empty> = Map::new("unpause_votes"); // member proxies
+
+// reported by the group's own proxies, federated ones report to their home group
+// all time by denom, swapped donations are counted in the group denom
+pub const TOTAL_DONATED: Map<&str, Uint128> = Map::new("total_donated");
+pub const LAST_DECAY: Item<u64> = Item::new("last_decay"); // latest performed update_weight
+
+pub const BADGE_CONTRACT: Item<Addr> = Item::new("badge_contract"); // cw721 membership badges
+pub const RECEIPT_CONTRACT: Item<Addr> = Item::new("receipt_contract"); // cw721 donor receipts
+
+// treasury is the membership contract balance
+pub const NEXT_SPEND_ID: Item<u64> = Item::new("next_spend_id");
+pub const SPEND_PROPOSALS: Map<u64, SpendProposal> = Map::new("spend_proposals");
+pub const SPEND_VOTES: Map<(u64, &Addr), Empty> = Map::new("spend_votes"); // (proposal, member proxy)
+
 pub const AWAITING_INITIAL_RESPS: Item<u64> = Item::new("awaiting_initial_resps");
+// kept only until the distribution contract is instantiated
+pub const INITIAL_MEMBERS: Item<Vec<Addr>> = Item::new("initial_members");
@@ -0,0 +1,5 @@
+use std::env::current_dir;
+
+fn main() {
+    proxy::schema::write_schema(&current_dir().unwrap().join("schema"));
+}
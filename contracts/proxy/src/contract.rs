@@ -1,10 +1,16 @@
 use cosmwasm_std::{
-    ensure, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+    ensure, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply,
+    Response, StdResult,
 };
 
+use common::validate::Validate;
+
 use crate::error::ContractError;
-use crate::msg::{ExecMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Config, CONFIG, DONATIONS, HALFTIME, LAST_UPDATED, OWNER, WEIGHT};
+use crate::msg::{ExecMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{
+    Config, Receipts, Swap, CONFIG, DONATIONS, HALFTIME, IBC_DENOMS, LAST_UPDATED, OWNER, RECEIPTS,
+    SWAP, WEIGHT,
+};
 
 mod exec;
 mod query;
@@ -12,15 +18,20 @@ mod reply;
 
 const WITHDRAW_REPLY_ID: u64 = 1;
 const PROPOSE_MEMBER_REPLY_ID: u64 = 2;
+const RECEIPT_REPLY_ID: u64 = 3;
+const SWAP_REPLY_ID: u64 = 4;
 
 pub fn instantiate(
     deps: DepsMut,
     env: Env,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    msg.validate()?;
+
+    let treasury_part = msg.treasury_part.unwrap_or_default();
     ensure!(
-        Decimal::zero() <= msg.direct_part && msg.direct_part <= Decimal::percent(100),
-        ContractError::InalidDirectPart
+        treasury_part <= Decimal::percent(100),
+        ContractError::InvalidTreasuryPart
     );
 
     let owner = deps.api.addr_validate(&msg.owner)?;
@@ -39,9 +50,32 @@ pub fn instantiate(
             distribution_contract,
             membership_contract,
             is_closed: false,
+            treasury_part,
         },
     )?;
     HALFTIME.save(deps.storage, &msg.halftime)?;
+
+    if let Some(receipts) = msg.receipts {
+        let receipts = Receipts {
+            contract: deps.api.addr_validate(&receipts.contract)?,
+            threshold: receipts.threshold,
+        };
+        RECEIPTS.save(deps.storage, &receipts)?;
+    }
+    if let Some(swap) = msg.swap {
+        ensure!(
+            swap.max_spread <= Decimal::percent(100),
+            ContractError::InvalidMaxSpread
+        );
+        let swap = Swap {
+            router: deps.api.addr_validate(&swap.router)?,
+            max_spread: swap.max_spread,
+        };
+        SWAP.save(deps.storage, &swap)?;
+    }
+    for ibc_denom in msg.ibc_denoms.unwrap_or_default() {
+        IBC_DENOMS.save(deps.storage, &ibc_denom.denom, &ibc_denom.origin)?;
+    }
     LAST_UPDATED.save(deps.storage, &env.block.time.seconds())?;
 
     Ok(Response::new())
@@ -56,22 +90,106 @@ pub fn execute(
     use ExecMsg::*;
 
     match msg {
-        Donate {} => exec::donate(deps, info),
-        Withdraw { receiver, amount } => exec::withdraw(deps, info, env, receiver, amount),
-        Close {} => exec::close(deps, info),
-        ProposeMember { addr } => exec::propose_member(deps, info, addr),
+        Donate {
+            campaign,
+            minimum_receive,
+        } => exec::donate(deps, env, info, campaign, minimum_receive),
+        Withdraw {
+            receiver,
+            amount,
+            denom,
+        } => exec::withdraw(deps, info, env, receiver, amount, denom),
+        Close {} => exec::close(deps, env, info),
+        ProposeMember { addr } => exec::propose_member(deps, env, info, addr),
         UpdateWeight {} => exec::update_weight(deps, env, info),
+        CreateCampaign {
+            title,
+            target,
+            deadline,
+        } => exec::create_campaign(deps, env, info, title, target, deadline),
+        CloseCampaign { id } => exec::close_campaign(deps, env, info, id),
+        ReclaimPledge { campaign, rollover } => {
+            exec::reclaim_pledge(deps, env, info, campaign, rollover)
+        }
+        TransferOwnership { new_owner, expiry } => {
+            exec::transfer_ownership(deps, env, info, new_owner, expiry)
+        }
+        AcceptOwnership {} => exec::accept_ownership(deps, env, info),
+        GrantOperator {
+            operator,
+            permissions,
+            expiry,
+        } => exec::grant_operator(deps, info, operator, permissions, expiry),
+        RevokeOperator { operator } => exec::revoke_operator(deps, info, operator),
+        SetWithdrawalPolicy {
+            allowed_receivers,
+            period_cap,
+            period,
+            timelock,
+            guardian,
+        } => exec::set_withdrawal_policy(
+            deps,
+            env,
+            info,
+            allowed_receivers,
+            period_cap,
+            period,
+            timelock,
+            guardian,
+        ),
+        ExecuteWithdrawal { id } => exec::execute_withdrawal(deps, env, info, id),
+        CancelWithdrawal { id } => exec::cancel_withdrawal(deps, env, info, id),
+        UpdateSplit { recipients } => exec::update_split(deps, info, recipients),
+        SetAutoForward { receiver } => exec::set_auto_forward(deps, env, info, receiver),
+        SetIbcDenom { denom, origin } => exec::set_ibc_denom(deps, info, denom, origin),
+        RemoveIbcDenom { denom } => exec::remove_ibc_denom(deps, info, denom),
+        SetPaused { paused } => exec::set_paused(deps, info, paused),
+        VoteUnpause {} => exec::vote_unpause(deps, env, info),
+        ProposeRemoval { member, reason } => exec::propose_removal(deps, env, info, member, reason),
+        Leave {} => exec::leave(deps, info),
+        Remove {} => exec::remove(deps, info),
+        ProposeSpend { msgs, description } => {
+            exec::propose_spend(deps, env, info, msgs, description)
+        }
+        VoteSpend { id } => exec::vote_spend(deps, env, info, id),
+        JoinGroup { membership, share } => exec::join_group(deps, info, membership, share),
+        GroupAccepted {
+            distribution_contract,
+        } => exec::group_accepted(deps, info, distribution_contract),
     }
 }
 
+pub fn migrate(_deps: DepsMut, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(Response::new().add_attribute("action", "migrate"))
+}
+
 pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, ContractError> {
     match reply.id {
         WITHDRAW_REPLY_ID => reply::withdraw(deps, env),
         PROPOSE_MEMBER_REPLY_ID => reply::propose_member(reply.result.into_result()),
+        RECEIPT_REPLY_ID => reply::receipt(reply.result.into_result()),
+        SWAP_REPLY_ID => reply::swapped(deps, env),
         id => Err(ContractError::UnrecognizedReplyId(id)),
     }
 }
 
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    Ok(Binary::default())
+    use QueryMsg::*;
+
+    match msg {
+        Campaign { id } => to_json_binary(&query::campaign(deps, id)?),
+        ListCampaigns { start_after, limit } => {
+            to_json_binary(&query::list_campaigns(deps, start_after, limit)?)
+        }
+        ListOperators { start_after, limit } => {
+            to_json_binary(&query::list_operators(deps, start_after, limit)?)
+        }
+        WithdrawalPolicy {} => to_json_binary(&query::withdrawal_policy(deps, env)?),
+        Split {} => to_json_binary(&query::split(deps)?),
+        AutoForward {} => to_json_binary(&query::auto_forward(deps)?),
+        QueuedWithdrawals { start_after, limit } => {
+            to_json_binary(&query::queued_withdrawals(deps, start_after, limit)?)
+        }
+        Groups {} => to_json_binary(&query::groups(deps)?),
+    }
 }
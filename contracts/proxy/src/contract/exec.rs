@@ -1,42 +1,594 @@
+use common::msg::{DonateData, PausableAction, PauseState, SpendMsg, UpdateWeightData};
 use cosmwasm_std::{
-    coins, ensure, to_json_binary, DepsMut, Env, MessageInfo, Response, StdResult, SubMsg, Uint128,
-    WasmMsg,
+    coin, coins, ensure, to_json_binary, Addr, BankMsg, Coin, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
-use cw_utils::must_pay;
+use cw_utils::{one_coin, PaymentError};
+use receipt::msg::{ExecMsg as ReceiptExecMsg, ReceiptMetadata};
 
-use crate::contract::{PROPOSE_MEMBER_REPLY_ID, WITHDRAW_REPLY_ID};
+use crate::contract::{
+    PROPOSE_MEMBER_REPLY_ID, RECEIPT_REPLY_ID, SWAP_REPLY_ID, WITHDRAW_REPLY_ID,
+};
 use crate::error::ContractError;
-use crate::msg::{DistribtionExecMsg, MembershipExecMsg};
+use crate::msg::{
+    AssetInfo, DistribtionExecMsg, MembershipExecMsg, RouterExecMsg, SplitRecipient, SwapOperation,
+};
 use crate::state::{
-    WithdrawalData, CONFIG, DONATIONS, HALFTIME, LAST_UPDATED, OWNER, PENDING_WITHDRAWAL, WEIGHT,
+    Campaign, CampaignStatus, Config, FederatedGroup, Operator, PendingOwner, PendingSwap,
+    PeriodWithdrawals, Permission, SplitShare, WithdrawalData, WithdrawalPolicy, AUTO_FORWARD,
+    AWAITING_WITHDRAWALS, CAMPAIGNS, CONFIG, DONATIONS, FEDERATED, HALFTIME, IBC_DENOMS,
+    LAST_UPDATED, NEXT_CAMPAIGN_ID, NEXT_RECEIPT_ID, OPERATORS, OPERATOR_WITHDRAWALS, OWNER,
+    PAUSED, PENDING_GROUPS, PENDING_OWNER, PENDING_SWAP, PENDING_WITHDRAWAL, PLEDGES,
+    QUEUED_WITHDRAWALS, RECEIPTS, REMOVED, SCHEDULED_POLICY, SPLIT, SWAP, WEIGHT,
+    WITHDRAWAL_POLICY,
 };
 
-pub fn donate(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let amount = must_pay(&info, &config.denom)?;
+fn ensure_not_paused(storage: &dyn Storage, action: PausableAction) -> Result<(), ContractError> {
+    let paused = PAUSED.may_load(storage)?.unwrap_or_default();
+    ensure!(!paused.is_paused(&action), ContractError::Paused);
+    Ok(())
+}
+
+// owner can do anything, operators only what one of their permissions allows
+fn ensure_permitted(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    allows: impl Fn(&Permission) -> bool,
+) -> Result<(), ContractError> {
+    if OWNER.load(deps.storage)? == *sender {
+        return Ok(());
+    }
+
+    let operator = OPERATORS
+        .may_load(deps.storage, sender)?
+        .ok_or(ContractError::Unauthorized)?;
+
+    let expired = operator
+        .expiry
+        .is_some_and(|expiry| expiry <= env.block.time.seconds());
+    ensure!(
+        !expired && operator.permissions.iter().any(allows),
+        ContractError::Unauthorized
+    );
+
+    Ok(())
+}
+
+// operators pay out only to the owner or the split payees, capped ones within the cap per period
+// caps are in the group denom, so capped operators withdraw nothing else
+fn operator_withdrawal(
+    storage: &mut dyn Storage,
+    env: &Env,
+    policy: &WithdrawalPolicy,
+    operator: &Addr,
+    receiver: &Option<Addr>,
+    amount: Option<Uint128>,
+    group_denom: bool,
+) -> Result<(), ContractError> {
+    if let Some(receiver) = receiver {
+        let allowed = *receiver == OWNER.load(storage)?
+            || payees(storage, None)?
+                .iter()
+                .any(|payee| payee.addr == *receiver);
+        ensure!(
+            allowed,
+            ContractError::ReceiverNotAllowed(receiver.to_string())
+        );
+    }
+
+    let caps: Vec<_> = OPERATORS
+        .load(storage, operator)?
+        .permissions
+        .into_iter()
+        .filter_map(|permission| match permission {
+            Permission::Withdraw { cap } => Some(cap),
+            _ => None,
+        })
+        .collect();
+    // any uncapped withdraw permission wins
+    let cap = match caps.into_iter().collect::<Option<Vec<_>>>() {
+        Some(caps) => caps.into_iter().max(),
+        None => return Ok(()),
+    };
+    let (Some(cap), Some(amount), true) = (cap, amount, group_denom) else {
+        return Err(ContractError::OperatorCapExceeded);
+    };
+
+    let now = env.block.time.seconds();
+    let mut withdrawn = OPERATOR_WITHDRAWALS
+        .may_load(storage, operator)?
+        .filter(|withdrawn| policy.period == 0 || now < withdrawn.start + policy.period)
+        .unwrap_or(PeriodWithdrawals {
+            start: now,
+            amount: Uint128::zero(),
+        });
+    withdrawn.amount += amount;
+    ensure!(withdrawn.amount <= cap, ContractError::OperatorCapExceeded);
+    OPERATOR_WITHDRAWALS.save(storage, operator, &withdrawn)?;
+
+    Ok(())
+}
+
+// scheduled policy replaces the current one once its time comes
+pub fn withdrawal_policy(storage: &mut dyn Storage, now: u64) -> StdResult<WithdrawalPolicy> {
+    if let Some((policy, at)) = SCHEDULED_POLICY.may_load(storage)? {
+        if at <= now {
+            WITHDRAWAL_POLICY.save(storage, &policy)?;
+            SCHEDULED_POLICY.remove(storage);
+            return Ok(policy);
+        }
+    }
+
+    Ok(WITHDRAWAL_POLICY.may_load(storage)?.unwrap_or_default())
+}
+
+// who gets what part of a withdrawal, without an explicit receiver it is the owner or the split
+pub fn payees(storage: &dyn Storage, receiver: Option<Addr>) -> StdResult<Vec<SplitShare>> {
+    if let Some(addr) = receiver {
+        return Ok(vec![SplitShare {
+            addr,
+            share: Decimal::one(),
+        }]);
+    }
+
+    match SPLIT.may_load(storage)? {
+        Some(split) => Ok(split),
+        None => Ok(vec![SplitShare {
+            addr: OWNER.load(storage)?,
+            share: Decimal::one(),
+        }]),
+    }
+}
+
+// last payee gets the rounding leftover, so the whole amount is always sent
+pub fn payout(payees: &[SplitShare], amount: Uint128, denom: &str) -> Vec<BankMsg> {
+    let mut left = amount;
+
+    payees
+        .iter()
+        .enumerate()
+        .map(|(idx, payee)| {
+            let part = if idx + 1 == payees.len() {
+                left
+            } else {
+                amount * payee.share
+            };
+            left -= part;
+            (payee, part)
+        })
+        .filter(|(_, part)| !part.is_zero())
+        .map(|(payee, part)| BankMsg::Send {
+            to_address: payee.addr.to_string(),
+            amount: coins(part.u128(), denom),
+        })
+        .collect()
+}
+
+// funds kept by the proxy which are not the owner's: escrowed pledges and queued withdrawals
+pub fn reserved(storage: &dyn Storage, config: &Config, denom: &str) -> StdResult<Uint128> {
+    // campaigns are raised in the group denom only
+    let pledged = PLEDGES
+        .range(storage, None, None, Order::Ascending)
+        .filter(|_| denom == config.denom)
+        .map(|pledge| pledge.map(|(_, amount)| amount));
+    let queued = QUEUED_WITHDRAWALS
+        .range(storage, None, None, Order::Ascending)
+        .filter(|withdrawal| {
+            withdrawal
+                .as_ref()
+                .map_or(true, |(_, withdrawal)| withdrawal.denom == denom)
+        })
+        .map(|withdrawal| withdrawal.map(|(_, withdrawal)| withdrawal.amount));
+
+    pledged.chain(queued).sum()
+}
+
+// group denom goes first, its amount is the one reported in the response data
+pub fn withdrawal_denoms(storage: &dyn Storage, config: &Config) -> StdResult<Vec<String>> {
+    std::iter::once(Ok(config.denom.clone()))
+        .chain(IBC_DENOMS.keys(storage, None, None, Order::Ascending))
+        .collect()
+}
+
+// weight becomes the donations count since the last withdrawal, returns old weight and the diff
+fn reset_weight(storage: &mut dyn Storage, now: u64) -> StdResult<(u64, i64)> {
+    let weight = WEIGHT.load(storage)?; // involvement
+    let donations = DONATIONS.load(storage)?;
+    let diff = donations as i64 - weight as i64;
+
+    WEIGHT.save(storage, &donations)?;
+    DONATIONS.save(storage, &1)?;
+    LAST_UPDATED.save(storage, &now)?;
+
+    Ok((weight, diff))
+}
+
+// membership contracts of the home group and the federated ones
+fn memberships(storage: &dyn Storage, config: &Config) -> StdResult<Vec<Addr>> {
+    let federated = FEDERATED.keys(storage, None, None, Order::Ascending);
+    std::iter::once(Ok(config.membership_contract.clone()))
+        .chain(federated)
+        .collect()
+}
+
+fn distributions(storage: &dyn Storage, config: &Config) -> StdResult<Vec<Addr>> {
+    let federated = FEDERATED
+        .range(storage, None, None, Order::Ascending)
+        .map(|group| group.map(|(_, group)| group.distribution));
+    std::iter::once(Ok(config.distribution_contract.clone()))
+        .chain(federated)
+        .collect()
+}
+
+fn distribution_withdraw_msg(distribution: &Addr, weight: u64, diff: i64) -> StdResult<WasmMsg> {
+    let withdraw_msg = DistribtionExecMsg::Withdraw { weight, diff };
+    let withdraw_msg = WasmMsg::Execute {
+        contract_addr: distribution.to_string(),
+        msg: to_json_binary(&withdraw_msg)?,
+        funds: vec![],
+    };
+    Ok(withdraw_msg)
+}
+
+// every distribution replies, the reply of the last one pays out
+fn withdraw_msgs(
+    storage: &mut dyn Storage,
+    config: &Config,
+    weight: u64,
+    diff: i64,
+) -> StdResult<Vec<SubMsg>> {
+    let distributions = distributions(storage, config)?;
+    AWAITING_WITHDRAWALS.save(storage, &(distributions.len() as u64))?;
+
+    distributions
+        .iter()
+        .map(|distribution| {
+            let withdraw_msg = distribution_withdraw_msg(distribution, weight, diff)?;
+            Ok(SubMsg::reply_on_success(withdraw_msg, WITHDRAW_REPLY_ID))
+        })
+        .collect()
+}
+
+// direct part, treasury slice and what is left for the distribution contracts
+fn split(config: &Config, amount: Uint128) -> (Uint128, Uint128, Uint128) {
+    let direct = amount * config.direct_part;
+    let treasury = (amount - direct) * config.treasury_part;
+    (direct, treasury, amount - direct - treasury)
+}
+
+// called once the donation is counted
+pub fn donate_data(
+    storage: &dyn Storage,
+    config: &Config,
+    amount: Uint128,
+) -> StdResult<DonateData> {
+    let (direct, treasury, distributed) = split(config, amount);
+    let data = DonateData {
+        direct,
+        treasury,
+        distributed,
+        donations: DONATIONS.load(storage)?,
+    };
+    Ok(data)
+}
+
+// direct part stays in this contract, the rest goes to the distribution contracts
+// treasury slice is taken from the distributed part and sent to membership
+// federated groups take their shares of what is left, the home group gets the rest
+fn distribute_msgs(
+    storage: &dyn Storage,
+    config: &Config,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+    let (_, treasury_amount, to_distribute) = split(config, amount);
+
+    let mut msgs = vec![];
+    if !treasury_amount.is_zero() {
+        msgs.push(SubMsg::new(BankMsg::Send {
+            to_address: config.membership_contract.to_string(),
+            amount: coins(treasury_amount.u128(), denom),
+        }));
+    }
+
+    let mut home_amount = to_distribute;
+    for group in FEDERATED.range(storage, None, None, Order::Ascending) {
+        let (_, group) = group?;
+        let group_amount = to_distribute * group.share;
+        if group_amount.is_zero() {
+            continue;
+        }
+        home_amount -= group_amount;
 
-    let direct_amount = amount * config.direct_part;
-    let to_distribute = amount - direct_amount;
+        let distribution_msg = WasmMsg::Execute {
+            contract_addr: group.distribution.into_string(),
+            msg: to_json_binary(&DistribtionExecMsg::Distribute {})?,
+            funds: coins(group_amount.u128(), denom),
+        };
+        msgs.push(SubMsg::new(distribution_msg));
+    }
 
     // 2 var with same name not a problem because the
     // WasmMsg will create first then assign to distribution_msg later
     let distribution_msg = DistribtionExecMsg::Distribute {};
     let distribution_msg = WasmMsg::Execute {
-        contract_addr: config.distribution_contract.into_string(),
+        contract_addr: config.distribution_contract.to_string(),
         msg: to_json_binary(&distribution_msg)?,
-        funds: coins(to_distribute.u128(), &config.denom),
+        funds: coins(home_amount.u128(), denom),
+    };
+    msgs.push(SubMsg::new(distribution_msg));
+
+    Ok(msgs)
+}
+
+// pledges get no receipt, only what was actually donated
+pub fn receipt_msg(
+    storage: &mut dyn Storage,
+    env: &Env,
+    donor: &Addr,
+    amount: Uint128,
+    denom: &str,
+) -> StdResult<Option<SubMsg>> {
+    let Some(receipts) = RECEIPTS.may_load(storage)? else {
+        return Ok(None);
+    };
+    if amount < receipts.threshold {
+        return Ok(None);
+    }
+
+    let id = NEXT_RECEIPT_ID.may_load(storage)?.unwrap_or_default();
+    NEXT_RECEIPT_ID.save(storage, &(id + 1))?;
+
+    let mint_msg = ReceiptExecMsg::Mint {
+        token_id: format!("{}/{}", env.contract.address, id),
+        owner: donor.to_string(),
+        token_uri: None,
+        extension: ReceiptMetadata {
+            creator: OWNER.load(storage)?.into_string(),
+            amount,
+            denom: denom.to_owned(),
+            donated_at: env.block.time.seconds(),
+        },
+    };
+    let mint_msg = WasmMsg::Execute {
+        contract_addr: receipts.contract.into_string(),
+        msg: to_json_binary(&mint_msg)?,
+        funds: vec![],
+    };
+
+    Ok(Some(SubMsg::reply_on_error(mint_msg, RECEIPT_REPLY_ID)))
+}
+
+// only the home group counts donations and decays of its proxies, federated groups don't
+fn stats_msg(config: &Config, msg: &MembershipExecMsg) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: config.membership_contract.to_string(),
+        msg: to_json_binary(msg)?,
+        funds: vec![],
+    })
+}
+
+// memberships pass weight changes to their cw4 hooks
+fn weight_changed_msgs(
+    storage: &dyn Storage,
+    config: &Config,
+    weight: u64,
+    diff: i64,
+) -> StdResult<Vec<WasmMsg>> {
+    if diff == 0 {
+        return Ok(vec![]);
+    }
+
+    let weight_msg = MembershipExecMsg::WeightChanged {
+        weight: (weight as i64 + diff) as u64,
     };
+    let weight_msg = to_json_binary(&weight_msg)?;
 
-    DONATIONS.update(deps.storage, |donations| -> StdResult<_> {
-        Ok(donations + 1)
+    let msgs = memberships(storage, config)?
+        .into_iter()
+        .map(|membership| WasmMsg::Execute {
+            contract_addr: membership.into_string(),
+            msg: weight_msg.clone(),
+            funds: vec![],
+        })
+        .collect();
+    Ok(msgs)
+}
+
+// counts `count` donations worth `amount` in total and splits them
+// with auto forward the direct part and the creator's share are withdrawn at once, like on withdraw
+pub fn donation_msgs(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    denom: &str,
+    amount: Uint128,
+    count: u64,
+) -> Result<Vec<SubMsg>, ContractError> {
+    DONATIONS.update(storage, |donations| -> StdResult<_> {
+        Ok(donations + count)
     })?;
 
+    let mut msgs = distribute_msgs(storage, config, denom, amount)?;
+    msgs.push(SubMsg::new(stats_msg(
+        config,
+        &MembershipExecMsg::Donated {
+            amount: coin(amount.u128(), denom),
+        },
+    )?));
+
+    let receiver = match AUTO_FORWARD.may_load(storage)? {
+        Some(receiver) => receiver,
+        None => return Ok(msgs),
+    };
+    // forwarding is a withdrawal, so it can't slip past a withdraw pause
+    ensure_not_paused(storage, PausableAction::Withdraw)?;
+
+    let (weight, diff) = reset_weight(storage, env.block.time.seconds())?;
+    let weight_msgs = weight_changed_msgs(storage, config, weight, diff)?;
+    msgs.extend(weight_msgs.into_iter().map(SubMsg::new));
+    // the direct part stays on the proxy and goes out with the withdrawal, under the same policy;
+    // a revenue split takes precedence over the forward receiver
+    let receiver = match SPLIT.may_load(storage)? {
+        Some(_) => None,
+        None => Some(receiver),
+    };
+    let denoms = withdrawal_denoms(storage, config)?;
+    PENDING_WITHDRAWAL.save(
+        storage,
+        &WithdrawalData {
+            receiver,
+            amount: None,
+            denoms,
+            weights: None,
+        },
+    )?;
+
+    msgs.extend(withdraw_msgs(storage, config, weight, diff)?);
+
+    Ok(msgs)
+}
+
+// any other token goes through the router first, the split follows in the swap reply
+fn swap(
+    deps: DepsMut,
+    env: Env,
+    config: &Config,
+    donor: Addr,
+    offer: Coin,
+    minimum_receive: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let swap = SWAP
+        .may_load(deps.storage)?
+        .ok_or_else(|| PaymentError::MissingDenom(config.denom.clone()))?;
+
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &config.denom)?;
+    PENDING_SWAP.save(
+        deps.storage,
+        &PendingSwap {
+            donor: donor.clone(),
+            offer: offer.clone(),
+            balance: balance.amount,
+            minimum_receive,
+        },
+    )?;
+
+    let swap_msg = RouterExecMsg::ExecuteSwapOperations {
+        operations: vec![SwapOperation::AstroSwap {
+            offer_asset_info: AssetInfo::NativeToken {
+                denom: offer.denom.clone(),
+            },
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: config.denom.clone(),
+            },
+        }],
+        minimum_receive,
+        to: None,
+        max_spread: Some(swap.max_spread),
+    };
+    let swap_msg = WasmMsg::Execute {
+        contract_addr: swap.router.into_string(),
+        msg: to_json_binary(&swap_msg)?,
+        funds: vec![offer.clone()],
+    };
+
     let resp = Response::new()
-        .add_message(distribution_msg)
+        .add_submessage(SubMsg::reply_on_success(swap_msg, SWAP_REPLY_ID))
+        .add_attribute("action", "donate")
+        .add_attribute("sender", donor.as_str())
+        .add_attribute("offer", offer.to_string());
+
+    Ok(resp)
+}
+
+pub fn donate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    campaign: Option<u64>,
+    minimum_receive: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, PausableAction::Donate)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(!config.is_closed, ContractError::Closed);
+    let Coin { denom, amount } = one_coin(&info)?;
+
+    if let Some(id) = campaign {
+        // campaigns are raised in the group denom only
+        ensure!(
+            denom == config.denom,
+            PaymentError::MissingDenom(config.denom.clone())
+        );
+        return pledge(deps, env, info.sender, id, amount);
+    }
+
+    // tokens of other chains come through IBC, e.g. an ICS-20 transfer with a wasm hook memo
+    let origin = IBC_DENOMS.may_load(deps.storage, &denom)?;
+    if denom != config.denom && origin.is_none() {
+        return swap(
+            deps,
+            env,
+            &config,
+            info.sender,
+            Coin { denom, amount },
+            minimum_receive,
+        );
+    }
+
+    let donation_msgs = donation_msgs(deps.storage, &env, &config, &denom, amount, 1)?;
+    let receipt_msg = receipt_msg(deps.storage, &env, &info.sender, amount, &denom)?;
+    let data = donate_data(deps.storage, &config, amount)?;
+
+    let mut resp = Response::new()
+        .add_submessages(donation_msgs)
+        .add_submessages(receipt_msg)
+        .set_data(to_json_binary(&data)?)
         .add_attribute("action", "donate")
         .add_attribute("sender", info.sender.as_str())
         .add_attribute("amount", amount.to_string());
 
+    if let Some(origin) = origin {
+        resp = resp
+            .add_attribute("denom", denom)
+            .add_attribute("origin", origin);
+    }
+
+    Ok(resp)
+}
+
+// campaign donations are kept here until the campaign is closed, so they can still be refunded
+fn pledge(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut campaign = CAMPAIGNS.load(deps.storage, id)?;
+    ensure!(
+        campaign.status == CampaignStatus::Active && env.block.time.seconds() < campaign.deadline,
+        ContractError::CampaignNotActive(id)
+    );
+
+    campaign.raised += amount;
+    campaign.donations += 1;
+    CAMPAIGNS.save(deps.storage, id, &campaign)?;
+
+    PLEDGES.update(deps.storage, (id, &sender), |pledged| -> StdResult<_> {
+        Ok(pledged.unwrap_or_default() + amount)
+    })?;
+
+    let resp = Response::new()
+        .add_attribute("action", "donate")
+        .add_attribute("sender", sender.as_str())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("campaign", id.to_string())
+        .add_attribute("raised", campaign.raised.to_string());
+
     Ok(resp)
 }
 
@@ -46,39 +598,83 @@ pub fn withdraw(
     env: Env,
     receiver: Option<String>,
     amount: Option<Uint128>,
+    denom: Option<String>,
 ) -> Result<Response, ContractError> {
-    let owner = OWNER.load(deps.storage)?;
-    ensure!(owner == info.sender, ContractError::Unauthorized);
-
-    let weight = WEIGHT.load(deps.storage)?; // involvement
-    let donations = DONATIONS.load(deps.storage)?;
-    let diff = donations as i64 - weight as i64;
-
-    WEIGHT.save(deps.storage, &donations)?;
-    DONATIONS.save(deps.storage, &1)?;
-    LAST_UPDATED.save(deps.storage, &env.block.time.seconds())?;
+    ensure_permitted(deps.as_ref(), &env, &info.sender, |permission| {
+        matches!(permission, Permission::Withdraw { .. })
+    })?;
+    ensure_not_paused(deps.storage, PausableAction::Withdraw)?;
 
+    // if do not specify receiver => withdraw to owner (or his collaborators), also when an operator withdraws
     let receiver = receiver
         .map(|addr_str| deps.api.addr_validate(&addr_str))
-        .transpose()?
-        .unwrap_or_else(|| info.sender.clone()); // if do not specify receiver => withdraw to owner
+        .transpose()?;
 
-    PENDING_WITHDRAWAL.save(deps.storage, &WithdrawalData { receiver, amount })?;
+    let config = CONFIG.load(deps.storage)?;
+    let denoms = match denom {
+        Some(denom) => {
+            ensure!(
+                denom == config.denom || IBC_DENOMS.has(deps.storage, &denom),
+                ContractError::UnknownDenom(denom)
+            );
+            vec![denom]
+        }
+        None if amount.is_some() => vec![config.denom.clone()],
+        None => withdrawal_denoms(deps.storage, &config)?,
+    };
 
-    let withdraw_msg = DistribtionExecMsg::Withdraw { weight, diff };
+    let policy = withdrawal_policy(deps.storage, env.block.time.seconds())?;
+    if OWNER.load(deps.storage)? != info.sender {
+        let group_denom = denoms == [config.denom.clone()];
+        operator_withdrawal(
+            deps.storage,
+            &env,
+            &policy,
+            &info.sender,
+            &receiver,
+            amount,
+            group_denom,
+        )?;
+    }
 
-    let config = CONFIG.load(deps.storage)?;
+    if let Some(allowed_receivers) = policy.allowed_receivers {
+        for payee in payees(deps.storage, receiver.clone())? {
+            ensure!(
+                allowed_receivers.contains(&payee.addr),
+                ContractError::ReceiverNotAllowed(payee.addr.into_string())
+            );
+        }
+    }
 
-    let withdraw_msg = WasmMsg::Execute {
-        contract_addr: config.distribution_contract.into_string(),
-        msg: to_json_binary(&withdraw_msg)?,
-        funds: vec![],
+    let mut pending = WithdrawalData {
+        receiver,
+        amount,
+        denoms,
+        weights: None,
     };
 
-    let withdraw_msg = SubMsg::reply_on_success(withdraw_msg, WITHDRAW_REPLY_ID);
+    // removed proxy got its last share from distribution on removal, only the balance is left
+    if REMOVED.may_load(deps.storage)?.unwrap_or(false) {
+        let weight = WEIGHT.load(deps.storage)?;
+        pending.weights = Some((weight, weight));
+        PENDING_WITHDRAWAL.save(deps.storage, &pending)?;
+
+        let resp = super::reply::withdraw(deps, env)?
+            .add_attribute("action", "withdraw")
+            .add_attribute("sender", info.sender.as_str());
+        return Ok(resp);
+    }
+
+    let (weight, diff) = reset_weight(deps.storage, env.block.time.seconds())?;
+    pending.weights = Some((weight, (weight as i64 + diff) as u64));
+    PENDING_WITHDRAWAL.save(deps.storage, &pending)?;
+
+    let weight_msgs = weight_changed_msgs(deps.storage, &config, weight, diff)?;
+    let withdraw_msgs = withdraw_msgs(deps.storage, &config, weight, diff)?;
 
     let resp = Response::new()
-        .add_submessage(withdraw_msg)
+        .add_submessages(withdraw_msgs)
+        .add_messages(weight_msgs)
         .add_attribute("action", "withdraw")
         .add_attribute("sender", info.sender.as_str())
         .add_attribute("new weight", weight.to_string());
@@ -86,9 +682,10 @@ pub fn withdraw(
     Ok(resp)
 }
 
-pub fn close(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    let owner = OWNER.load(deps.storage)?;
-    ensure!(owner == info.sender, ContractError::Unauthorized);
+pub fn close(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    ensure_permitted(deps.as_ref(), &env, &info.sender, |permission| {
+        *permission == Permission::Close
+    })?;
 
     CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
         config.is_closed = true;
@@ -101,12 +698,15 @@ pub fn close(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError
 
 pub fn propose_member(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     addr: String,
 ) -> Result<Response, ContractError> {
-    // check this is send by owner (content creator) of this proxy contract
-    let owner = OWNER.load(deps.storage)?;
-    ensure!(owner == info.sender, ContractError::Unauthorized);
+    // check this is send by owner (content creator) of this proxy contract or his voting operator
+    ensure_permitted(deps.as_ref(), &env, &info.sender, |permission| {
+        *permission == Permission::Vote
+    })?;
+    ensure_not_paused(deps.storage, PausableAction::Propose)?;
 
     let config = CONFIG.load(deps.storage)?;
 
@@ -131,6 +731,8 @@ pub fn update_weight(
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, PausableAction::Decay)?;
+
     let last_updated = LAST_UPDATED.load(deps.storage)?;
     let halftime = HALFTIME.load(deps.storage)?;
 
@@ -146,10 +748,20 @@ pub fn update_weight(
     // last_updated = 19/11/2020 00:00:00
     // if halftime < current_time - last_updated => update_weight
 
+    // removed proxy has no weight left to decay and membership no longer takes its stats
+    let removed = REMOVED.may_load(deps.storage)?.unwrap_or(false);
     let elapsed_time = env.block.time.seconds() - last_updated;
-    if halftime > elapsed_time {
+    if removed || halftime > elapsed_time {
         // not yet halftime
-        let resp = resp.add_attribute("performed", "no");
+        let weight = WEIGHT.load(deps.storage)?;
+        let data = UpdateWeightData {
+            performed: false,
+            old_weight: weight,
+            new_weight: weight,
+        };
+        let resp = resp
+            .add_attribute("performed", "no")
+            .set_data(to_json_binary(&data)?);
         return Ok(resp);
     }
 
@@ -161,20 +773,802 @@ pub fn update_weight(
     let diff = -(weight as i64) / 2; // why minus here?
 
     // when force update => withdraw the share fund into proxy contract
-    let withdraw_msg = DistribtionExecMsg::Withdraw { weight, diff };
-    let withdraw_msg = WasmMsg::Execute {
-        contract_addr: config.distribution_contract.into_string(),
-        msg: to_json_binary(&withdraw_msg)?,
-        funds: vec![],
-    };
+    let withdraw_msgs = distributions(deps.storage, &config)?
+        .iter()
+        .map(|distribution| distribution_withdraw_msg(distribution, weight, diff))
+        .collect::<StdResult<Vec<_>>>()?;
+    let weight_msgs = weight_changed_msgs(deps.storage, &config, weight, diff)?;
 
-    WEIGHT.update(deps.storage, |weight: u64| -> StdResult<_> {
+    let new_weight = WEIGHT.update(deps.storage, |weight: u64| -> StdResult<_> {
         Ok((weight as i64 + diff) as u64)
     })?;
+    let data = UpdateWeightData {
+        performed: true,
+        old_weight: weight,
+        new_weight,
+    };
 
     let resp = resp
-        .add_message(withdraw_msg)
-        .add_attribute("new weight", WEIGHT.load(deps.storage)?.to_string());
+        .add_messages(withdraw_msgs)
+        .add_messages(weight_msgs)
+        .add_message(stats_msg(&config, &MembershipExecMsg::Decayed {})?)
+        .add_attribute("new weight", new_weight.to_string())
+        .set_data(to_json_binary(&data)?);
+
+    Ok(resp)
+}
+
+pub fn create_campaign(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    target: Uint128,
+    deadline: u64,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+    ensure!(
+        deadline > env.block.time.seconds(),
+        ContractError::InvalidDeadline
+    );
+
+    let id = NEXT_CAMPAIGN_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_CAMPAIGN_ID.save(deps.storage, &(id + 1))?;
+
+    let campaign = Campaign {
+        title,
+        target,
+        deadline,
+        raised: Uint128::zero(),
+        donations: 0,
+        status: CampaignStatus::Active,
+    };
+    CAMPAIGNS.save(deps.storage, id, &campaign)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "create campaign")
+        .add_attribute("campaign", id.to_string())
+        .add_attribute("target", target.to_string())
+        .add_attribute("deadline", deadline.to_string());
+    Ok(resp)
+}
+
+// anyone can close a campaign once its target is reached or its deadline passed
+pub fn close_campaign(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    // successful campaign is donated at once
+    ensure_not_paused(deps.storage, PausableAction::Donate)?;
+
+    let mut campaign = CAMPAIGNS.load(deps.storage, id)?;
+    ensure!(
+        campaign.status == CampaignStatus::Active,
+        ContractError::CampaignNotActive(id)
+    );
+
+    let reached = campaign.raised >= campaign.target;
+    ensure!(
+        reached || env.block.time.seconds() >= campaign.deadline,
+        ContractError::CampaignInProgress(id)
+    );
+
+    let resp = Response::new()
+        .add_attribute("action", "close campaign")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("campaign", id.to_string());
+
+    if !reached {
+        // pledges stay escrowed until donors reclaim them
+        campaign.status = CampaignStatus::Failed;
+        CAMPAIGNS.save(deps.storage, id, &campaign)?;
+        return Ok(resp.add_attribute("status", "failed"));
+    }
+
+    campaign.status = CampaignStatus::Succeeded;
+    CAMPAIGNS.save(deps.storage, id, &campaign)?;
+
+    // pledged funds are not escrowed anymore
+    let donors = PLEDGES
+        .prefix(id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for donor in donors {
+        PLEDGES.remove(deps.storage, (id, &donor));
+    }
+
+    // every pledge counts as a donation once the campaign succeeded
+    let config = CONFIG.load(deps.storage)?;
+    let donation_msgs = donation_msgs(
+        deps.storage,
+        &env,
+        &config,
+        &config.denom,
+        campaign.raised,
+        campaign.donations,
+    )?;
+
+    let resp = resp
+        .add_submessages(donation_msgs)
+        .add_attribute("status", "succeeded")
+        .add_attribute("amount", campaign.raised.to_string());
+
+    Ok(resp)
+}
+
+pub fn reclaim_pledge(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    campaign: u64,
+    rollover: bool,
+) -> Result<Response, ContractError> {
+    let action = match rollover {
+        true => PausableAction::Donate,
+        false => PausableAction::Withdraw,
+    };
+    ensure_not_paused(deps.storage, action)?;
+
+    let status = CAMPAIGNS.load(deps.storage, campaign)?.status;
+    ensure!(
+        status == CampaignStatus::Failed,
+        ContractError::CampaignNotFailed(campaign)
+    );
+
+    let amount = PLEDGES
+        .may_load(deps.storage, (campaign, &info.sender))?
+        .ok_or(ContractError::NoPledge)?;
+    PLEDGES.remove(deps.storage, (campaign, &info.sender));
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "reclaim pledge")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("campaign", campaign.to_string())
+        .add_attribute("amount", amount.to_string());
+
+    if rollover {
+        let donation_msgs = donation_msgs(deps.storage, &env, &config, &config.denom, amount, 1)?;
+
+        let resp = resp
+            .add_submessages(donation_msgs)
+            .add_attribute("rollover", "yes");
+        return Ok(resp);
+    }
+
+    let bank_msg = BankMsg::Send {
+        to_address: info.sender.into_string(),
+        amount: coins(amount.u128(), &config.denom),
+    };
+
+    Ok(resp.add_message(bank_msg).add_attribute("rollover", "no"))
+}
+
+// first step of the transfer, the new owner has to accept it before `expiry`
+pub fn transfer_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: String,
+    expiry: Option<u64>,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+
+    if let Some(expiry) = expiry {
+        ensure!(
+            expiry > env.block.time.seconds(),
+            ContractError::InvalidExpiry
+        );
+    }
+
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+    PENDING_OWNER.save(
+        deps.storage,
+        &PendingOwner {
+            owner: new_owner.clone(),
+            expiry,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "transfer ownership")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("new_owner", new_owner.as_str());
+    Ok(resp)
+}
+
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_OWNER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingOwner)?;
+    ensure!(pending.owner == info.sender, ContractError::Unauthorized);
+
+    if let Some(expiry) = pending.expiry {
+        ensure!(
+            env.block.time.seconds() < expiry,
+            ContractError::OwnershipTransferExpired
+        );
+    }
+
+    let previous_owner = OWNER.load(deps.storage)?;
+    OWNER.save(deps.storage, &info.sender)?;
+    PENDING_OWNER.remove(deps.storage);
+
+    // memberships keep track of owners, they also reject the new owner if he is already a member
+    let config = CONFIG.load(deps.storage)?;
+    let transferred_msg = MembershipExecMsg::OwnershipTransferred {
+        previous_owner: previous_owner.to_string(),
+        new_owner: info.sender.to_string(),
+    };
+    let transferred_msg = to_json_binary(&transferred_msg)?;
+    let transferred_msgs = memberships(deps.storage, &config)?
+        .into_iter()
+        .map(|membership| WasmMsg::Execute {
+            contract_addr: membership.into_string(),
+            msg: transferred_msg.clone(),
+            funds: vec![],
+        });
 
+    let resp = Response::new()
+        .add_messages(transferred_msgs)
+        .add_attribute("action", "accept ownership")
+        .add_attribute("previous_owner", previous_owner.as_str())
+        .add_attribute("new_owner", info.sender.as_str());
+    Ok(resp)
+}
+
+pub fn grant_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+    permissions: Vec<Permission>,
+    expiry: Option<u64>,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+
+    // granting again replaces previous permissions and starts the cap over
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATOR_WITHDRAWALS.remove(deps.storage, &operator);
+    OPERATORS.save(
+        deps.storage,
+        &operator,
+        &Operator {
+            permissions,
+            expiry,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "grant operator")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("operator", operator.as_str());
+    Ok(resp)
+}
+
+pub fn revoke_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATORS.remove(deps.storage, &operator);
+    OPERATOR_WITHDRAWALS.remove(deps.storage, &operator);
+
+    let resp = Response::new()
+        .add_attribute("action", "revoke operator")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("operator", operator.as_str());
+    Ok(resp)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn set_withdrawal_policy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    allowed_receivers: Option<Vec<String>>,
+    period_cap: Option<Uint128>,
+    period: u64,
+    timelock: u64,
+    guardian: Option<String>,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+    ensure!(
+        period_cap.is_none() || period > 0,
+        ContractError::InvalidWithdrawalPeriod
+    );
+
+    let allowed_receivers = allowed_receivers
+        .map(|receivers| {
+            receivers
+                .iter()
+                .map(|receiver| deps.api.addr_validate(receiver))
+                .collect::<StdResult<Vec<_>>>()
+        })
+        .transpose()?;
+    let guardian = guardian
+        .map(|guardian| deps.api.addr_validate(&guardian))
+        .transpose()?;
+
+    let policy = WithdrawalPolicy {
+        allowed_receivers,
+        period_cap,
+        period,
+        timelock,
+        guardian,
+    };
+
+    // changes wait for the current timelock, otherwise a leaked key could just lift the limits
+    let now = env.block.time.seconds();
+    let at = now + withdrawal_policy(deps.storage, now)?.timelock;
+    if at > now {
+        SCHEDULED_POLICY.save(deps.storage, &(policy, at))?;
+    } else {
+        WITHDRAWAL_POLICY.save(deps.storage, &policy)?;
+        SCHEDULED_POLICY.remove(deps.storage);
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "set withdrawal policy")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("effective_at", at.to_string());
+    Ok(resp)
+}
+
+// anyone can release a queued withdrawal after its timelock, the receiver is fixed anyway
+pub fn execute_withdrawal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, PausableAction::Withdraw)?;
+
+    let withdrawal = QUEUED_WITHDRAWALS.load(deps.storage, id)?;
+    ensure!(
+        withdrawal.release_at <= env.block.time.seconds(),
+        ContractError::WithdrawalLocked(id)
+    );
+    QUEUED_WITHDRAWALS.remove(deps.storage, id);
+
+    let payees = payees(deps.storage, withdrawal.receiver)?;
+
+    let resp = Response::new()
+        .add_messages(payout(&payees, withdrawal.amount, &withdrawal.denom))
+        .add_attribute("action", "execute withdrawal")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("id", id.to_string())
+        .add_attribute("amount", withdrawal.amount.to_string());
+    Ok(resp)
+}
+
+pub fn cancel_withdrawal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    let guardian = withdrawal_policy(deps.storage, env.block.time.seconds())?.guardian;
+    ensure!(
+        owner == info.sender || guardian.as_ref() == Some(&info.sender),
+        ContractError::Unauthorized
+    );
+
+    // funds stay in the proxy
+    let withdrawal = QUEUED_WITHDRAWALS.load(deps.storage, id)?;
+    QUEUED_WITHDRAWALS.remove(deps.storage, id);
+
+    let resp = Response::new()
+        .add_attribute("action", "cancel withdrawal")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("id", id.to_string())
+        .add_attribute("amount", withdrawal.amount.to_string());
+    Ok(resp)
+}
+
+pub fn update_split(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipients: Vec<SplitRecipient>,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+
+    if recipients.is_empty() {
+        SPLIT.remove(deps.storage);
+        let resp = Response::new()
+            .add_attribute("action", "update split")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("recipients", "0");
+        return Ok(resp);
+    }
+
+    let mut split: Vec<SplitShare> = Vec::with_capacity(recipients.len());
+    for recipient in recipients {
+        let addr = deps.api.addr_validate(&recipient.addr)?;
+        ensure!(
+            !recipient.share.is_zero() && split.iter().all(|share| share.addr != addr),
+            ContractError::InvalidSplit
+        );
+        split.push(SplitShare {
+            addr,
+            share: recipient.share,
+        });
+    }
+
+    let total = split
+        .iter()
+        .try_fold(Decimal::zero(), |total, share| {
+            total.checked_add(share.share)
+        })
+        .map_err(|_| ContractError::InvalidSplit)?;
+    ensure!(total == Decimal::one(), ContractError::InvalidSplit);
+
+    SPLIT.save(deps.storage, &split)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "update split")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("recipients", split.len().to_string());
+    Ok(resp)
+}
+
+pub fn set_auto_forward(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+
+    let resp = Response::new()
+        .add_attribute("action", "set auto forward")
+        .add_attribute("sender", info.sender.as_str());
+
+    let receiver = match receiver {
+        Some(receiver) => deps.api.addr_validate(&receiver)?,
+        None => {
+            AUTO_FORWARD.remove(deps.storage);
+            return Ok(resp.add_attribute("receiver", "none"));
+        }
+    };
+
+    // forwarding is a withdrawal on every donation, so it has to go to an allowed receiver
+    let policy = withdrawal_policy(deps.storage, env.block.time.seconds())?;
+    if let Some(allowed_receivers) = policy.allowed_receivers {
+        ensure!(
+            allowed_receivers.contains(&receiver),
+            ContractError::ReceiverNotAllowed(receiver.into_string())
+        );
+    }
+
+    AUTO_FORWARD.save(deps.storage, &receiver)?;
+
+    Ok(resp.add_attribute("receiver", receiver.as_str()))
+}
+
+pub fn set_ibc_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    origin: String,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(denom != config.denom, ContractError::GroupDenom(denom));
+
+    IBC_DENOMS.save(deps.storage, &denom, &origin)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "set ibc denom")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("denom", denom)
+        .add_attribute("origin", origin);
+    Ok(resp)
+}
+
+pub fn remove_ibc_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+    ensure!(
+        IBC_DENOMS.has(deps.storage, &denom),
+        ContractError::UnknownDenom(denom)
+    );
+
+    IBC_DENOMS.remove(deps.storage, &denom);
+
+    let resp = Response::new()
+        .add_attribute("action", "remove ibc denom")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("denom", denom);
+    Ok(resp)
+}
+
+pub fn set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: PauseState,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        config.membership_contract == info.sender,
+        ContractError::Unauthorized
+    );
+
+    PAUSED.save(deps.storage, &paused)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "set paused")
+        .add_attribute("all", paused.all.to_string());
+    Ok(resp)
+}
+
+// unpausing is decided by the group, same as accepting a new member
+pub fn vote_unpause(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    ensure_permitted(deps.as_ref(), &env, &info.sender, |permission| {
+        *permission == Permission::Vote
+    })?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let vote_msg = MembershipExecMsg::ProposeUnpause {};
+    let vote_msg = WasmMsg::Execute {
+        contract_addr: config.membership_contract.into_string(),
+        msg: to_json_binary(&vote_msg)?,
+        funds: vec![],
+    };
+
+    let resp = Response::new()
+        .add_message(vote_msg)
+        .add_attribute("action", "vote unpause")
+        .add_attribute("sender", info.sender.as_str());
+    Ok(resp)
+}
+
+pub fn propose_removal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    member: String,
+    reason: String,
+) -> Result<Response, ContractError> {
+    ensure_permitted(deps.as_ref(), &env, &info.sender, |permission| {
+        *permission == Permission::Vote
+    })?;
+    ensure_not_paused(deps.storage, PausableAction::Propose)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let proposal_msg = MembershipExecMsg::ProposeRemoval {
+        member: member.clone(),
+        reason,
+    };
+    let proposal_msg = WasmMsg::Execute {
+        contract_addr: config.membership_contract.into_string(),
+        msg: to_json_binary(&proposal_msg)?,
+        funds: vec![],
+    };
+
+    let resp = Response::new()
+        .add_message(proposal_msg)
+        .add_attribute("action", "propose removal")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("member", member);
+    Ok(resp)
+}
+
+// membership removes the proxy and sends Remove back, the same as after a vote
+pub fn leave(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+
+    let config = CONFIG.load(deps.storage)?;
+    let leave_msg = WasmMsg::Execute {
+        contract_addr: config.membership_contract.into_string(),
+        msg: to_json_binary(&MembershipExecMsg::Leave {})?,
+        funds: vec![],
+    };
+
+    let resp = Response::new()
+        .add_message(leave_msg)
+        .add_attribute("action", "leave")
+        .add_attribute("owner", owner.as_str());
+    Ok(resp)
+}
+
+// the whole weight goes back to distribution, what was earned so far is sent here with the same message
+// federated group removes the proxy only from itself, the home group closes it in all of them
+pub fn remove(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let weight = WEIGHT.load(deps.storage)?;
+
+    if let Some(group) = FEDERATED.may_load(deps.storage, &info.sender)? {
+        FEDERATED.remove(deps.storage, &info.sender);
+
+        let withdraw_msg =
+            distribution_withdraw_msg(&group.distribution, weight, -(weight as i64))?;
+        let resp = Response::new()
+            .add_message(withdraw_msg)
+            .add_attribute("action", "remove")
+            .add_attribute("group", info.sender.as_str());
+        return Ok(resp);
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.membership_contract,
+        ContractError::Unauthorized
+    );
+
+    config.is_closed = true;
+    CONFIG.save(deps.storage, &config)?;
+    REMOVED.save(deps.storage, &true)?;
+
+    WEIGHT.save(deps.storage, &0)?;
+    DONATIONS.save(deps.storage, &0)?;
+
+    let withdraw_msgs = distributions(deps.storage, &config)?
+        .iter()
+        .map(|distribution| distribution_withdraw_msg(distribution, weight, -(weight as i64)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let resp = Response::new()
+        .add_messages(withdraw_msgs)
+        .add_attribute("action", "remove");
+    Ok(resp)
+}
+
+pub fn propose_spend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<SpendMsg>,
+    description: String,
+) -> Result<Response, ContractError> {
+    ensure_permitted(deps.as_ref(), &env, &info.sender, |permission| {
+        *permission == Permission::Vote
+    })?;
+    ensure_not_paused(deps.storage, PausableAction::Propose)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let proposal_msg = MembershipExecMsg::ProposeSpend { msgs, description };
+    let proposal_msg = WasmMsg::Execute {
+        contract_addr: config.membership_contract.into_string(),
+        msg: to_json_binary(&proposal_msg)?,
+        funds: vec![],
+    };
+
+    let resp = Response::new()
+        .add_message(proposal_msg)
+        .add_attribute("action", "propose spend")
+        .add_attribute("sender", info.sender.as_str());
+    Ok(resp)
+}
+
+pub fn vote_spend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    ensure_permitted(deps.as_ref(), &env, &info.sender, |permission| {
+        *permission == Permission::Vote
+    })?;
+    ensure_not_paused(deps.storage, PausableAction::Propose)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let vote_msg = MembershipExecMsg::VoteSpend { id };
+    let vote_msg = WasmMsg::Execute {
+        contract_addr: config.membership_contract.into_string(),
+        msg: to_json_binary(&vote_msg)?,
+        funds: vec![],
+    };
+
+    let resp = Response::new()
+        .add_message(vote_msg)
+        .add_attribute("action", "vote spend")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("id", id.to_string());
+    Ok(resp)
+}
+
+// requested shares of all groups can not leave the home group with less than nothing
+pub fn join_group(
+    deps: DepsMut,
+    info: MessageInfo,
+    membership: String,
+    share: Decimal,
+) -> Result<Response, ContractError> {
+    let owner = OWNER.load(deps.storage)?;
+    ensure!(owner == info.sender, ContractError::Unauthorized);
+
+    let membership = deps.api.addr_validate(&membership)?;
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        membership != config.membership_contract && !FEDERATED.has(deps.storage, &membership),
+        ContractError::AlreadyInGroup
+    );
+
+    let federated = FEDERATED
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|group| group.map(|(_, group)| group.share));
+    let pending = PENDING_GROUPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|group| {
+            group
+                .as_ref()
+                .map_or(true, |(pending, _)| *pending != membership)
+        })
+        .map(|group| group.map(|(_, share)| share));
+    let shares = federated
+        .chain(pending)
+        .sum::<StdResult<Decimal>>()?
+        .checked_add(share)
+        .map_err(StdError::from)?;
+    ensure!(
+        !share.is_zero() && shares <= Decimal::one(),
+        ContractError::InvalidGroupShare
+    );
+
+    PENDING_GROUPS.save(deps.storage, &membership, &share)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "join group")
+        .add_attribute("membership", membership.as_str())
+        .add_attribute("share", share.to_string());
+    Ok(resp)
+}
+
+// the proxy enters the new distribution with its current weight
+pub fn group_accepted(
+    deps: DepsMut,
+    info: MessageInfo,
+    distribution_contract: String,
+) -> Result<Response, ContractError> {
+    let share = PENDING_GROUPS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized)?;
+    PENDING_GROUPS.remove(deps.storage, &info.sender);
+
+    let distribution = deps.api.addr_validate(&distribution_contract)?;
+    FEDERATED.save(
+        deps.storage,
+        &info.sender,
+        &FederatedGroup {
+            distribution: distribution.clone(),
+            share,
+        },
+    )?;
+
+    let weight = WEIGHT.load(deps.storage)?;
+    let withdraw_msg = distribution_withdraw_msg(&distribution, 0, weight as i64)?;
+
+    let resp = Response::new()
+        .add_message(withdraw_msg)
+        .add_attribute("action", "group accepted")
+        .add_attribute("membership", info.sender.as_str());
     Ok(resp)
 }
@@ -0,0 +1,176 @@
+use cosmwasm_std::{Addr, Decimal, Deps, Env, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::{
+    msg::{
+        AutoForwardResponse, CampaignResponse, GroupResponse, GroupsResponse,
+        ListCampaignsResponse, ListOperatorsResponse, OperatorResponse, QueuedWithdrawalResponse,
+        QueuedWithdrawalsResponse, SplitRecipient, SplitResponse, WithdrawalPolicyResponse,
+    },
+    state::{
+        Campaign, AUTO_FORWARD, CAMPAIGNS, CONFIG, FEDERATED, OPERATORS, QUEUED_WITHDRAWALS,
+        SCHEDULED_POLICY, SPLIT, WITHDRAWAL_POLICY,
+    },
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+fn campaign_response(id: u64, campaign: Campaign) -> CampaignResponse {
+    CampaignResponse {
+        id,
+        title: campaign.title,
+        target: campaign.target,
+        deadline: campaign.deadline,
+        raised: campaign.raised,
+        status: campaign.status,
+    }
+}
+
+pub fn campaign(deps: Deps, id: u64) -> StdResult<CampaignResponse> {
+    let campaign = CAMPAIGNS.load(deps.storage, id)?;
+    Ok(campaign_response(id, campaign))
+}
+
+pub fn list_campaigns(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListCampaignsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let campaigns = CAMPAIGNS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|campaign| -> StdResult<_> {
+            let (id, campaign) = campaign?;
+            Ok(campaign_response(id, campaign))
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListCampaignsResponse { campaigns })
+}
+
+pub fn list_operators(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListOperatorsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let operators = OPERATORS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|operator| -> StdResult<_> {
+            let (addr, operator) = operator?;
+            Ok(OperatorResponse {
+                addr: addr.into_string(),
+                permissions: operator.permissions,
+                expiry: operator.expiry,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListOperatorsResponse { operators })
+}
+
+pub fn withdrawal_policy(deps: Deps, env: Env) -> StdResult<WithdrawalPolicyResponse> {
+    let policy = WITHDRAWAL_POLICY
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    // scheduled policy is applied lazily, so it may already be the one in force
+    let resp = match SCHEDULED_POLICY.may_load(deps.storage)? {
+        Some((scheduled, at)) if at <= env.block.time.seconds() => WithdrawalPolicyResponse {
+            policy: scheduled,
+            scheduled: None,
+            scheduled_at: None,
+        },
+        Some((scheduled, at)) => WithdrawalPolicyResponse {
+            policy,
+            scheduled: Some(scheduled),
+            scheduled_at: Some(at),
+        },
+        None => WithdrawalPolicyResponse {
+            policy,
+            scheduled: None,
+            scheduled_at: None,
+        },
+    };
+
+    Ok(resp)
+}
+
+pub fn queued_withdrawals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<QueuedWithdrawalsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let withdrawals = QUEUED_WITHDRAWALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|withdrawal| -> StdResult<_> {
+            let (id, withdrawal) = withdrawal?;
+            Ok(QueuedWithdrawalResponse {
+                id,
+                receiver: withdrawal.receiver.map(Addr::into_string),
+                amount: withdrawal.amount,
+                denom: withdrawal.denom,
+                release_at: withdrawal.release_at,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(QueuedWithdrawalsResponse { withdrawals })
+}
+
+pub fn split(deps: Deps) -> StdResult<SplitResponse> {
+    let recipients = SPLIT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|share| SplitRecipient {
+            addr: share.addr.into_string(),
+            share: share.share,
+        })
+        .collect();
+
+    Ok(SplitResponse { recipients })
+}
+
+pub fn auto_forward(deps: Deps) -> StdResult<AutoForwardResponse> {
+    let receiver = AUTO_FORWARD.may_load(deps.storage)?.map(Addr::into_string);
+    Ok(AutoForwardResponse { receiver })
+}
+
+pub fn groups(deps: Deps) -> StdResult<GroupsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let federated = FEDERATED
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|group| -> StdResult<_> {
+            let (membership, group) = group?;
+            Ok(GroupResponse {
+                membership: membership.into_string(),
+                distribution: group.distribution.into_string(),
+                share: group.share,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let home = GroupResponse {
+        membership: config.membership_contract.into_string(),
+        distribution: config.distribution_contract.into_string(),
+        share: federated
+            .iter()
+            .fold(Decimal::one(), |share, group| share - group.share),
+    };
+
+    let groups = std::iter::once(home).chain(federated).collect();
+    Ok(GroupsResponse { groups })
+}
@@ -1,8 +1,20 @@
-use cosmwasm_std::{coins, BankMsg, DepsMut, Env, Response, StdError, SubMsgResponse};
+use common::msg::WithdrawData;
+use cosmwasm_std::{
+    ensure, to_json_binary, Addr, BankMsg, Binary, DepsMut, Env, Response, StdError, StdResult,
+    Storage, SubMsgResponse, Uint128,
+};
 
 use crate::{
     error::ContractError,
-    state::{CONFIG, PENDING_WITHDRAWAL},
+    state::{
+        Config, PendingWithdrawal, PeriodWithdrawals, WithdrawalData, WithdrawalPolicy,
+        AWAITING_WITHDRAWALS, CONFIG, NEXT_WITHDRAWAL_ID, PENDING_SWAP, PENDING_WITHDRAWAL,
+        PERIOD_WITHDRAWALS, QUEUED_WITHDRAWALS,
+    },
+};
+
+use super::exec::{
+    donate_data, donation_msgs, payees, payout, receipt_msg, reserved, withdrawal_policy,
 };
 
 // distribution contract send reply to proxy contract when it finish in handle the withdraw message sent from proxy contract (that mean the distribution contract has already send token to proxy contract)
@@ -13,30 +25,137 @@ pub fn withdraw(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
     // when the execution entry point finished (withdraw handler proxy contract) and message is processed as part of transaction (in this case is the withdraw message sent from proxy contract to distribution contract) then we lost all the information that we pass with withdraw message to proxy contract
     let withdraw_info = PENDING_WITHDRAWAL.load(deps.storage)?;
 
+    // with federated groups every distribution replies, only the last one pays out
+    let awaiting = AWAITING_WITHDRAWALS
+        .may_load(deps.storage)?
+        .unwrap_or(1)
+        .saturating_sub(1);
+    if awaiting > 0 {
+        AWAITING_WITHDRAWALS.save(deps.storage, &awaiting)?;
+        return Ok(Response::new());
+    }
+    AWAITING_WITHDRAWALS.remove(deps.storage);
+
     let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let policy = withdrawal_policy(deps.storage, now)?;
 
-    // remember the distribution contract may has already send distributed token of owner to proxy contract => so we just need query balance of proxy contract
-    // balance of proxy contract consists of 2 part
-    let total_amount = deps
-        .querier
-        .query_balance(env.contract.address, &config.denom)?;
+    let mut resp = Response::new();
+    for (idx, denom) in withdraw_info.denoms.iter().enumerate() {
+        // remember the distribution contract may has already send distributed token of owner to proxy contract => so we just need query balance of proxy contract
+        let balance = deps.querier.query_balance(&env.contract.address, denom)?;
 
-    let amount = withdraw_info.amount.unwrap_or(total_amount.amount);
+        // campaign pledges and queued withdrawals are not the owner's to take
+        let available = balance
+            .amount
+            .checked_sub(reserved(deps.storage, &config, denom)?)
+            .map_err(StdError::from)?;
 
-    // send token to receiver
+        let amount = withdraw_info.amount.unwrap_or(available);
+        ensure!(
+            amount <= available,
+            ContractError::InsufficientFunds {
+                requested: amount,
+                available,
+            }
+        );
 
-    let bank_msg = BankMsg::Send {
-        to_address: withdraw_info.receiver.into_string(),
-        amount: coins(amount.u128(), &config.denom),
-    };
+        let queued = if amount.is_zero() {
+            None
+        } else {
+            let (msgs, queued) = pay_or_queue(
+                deps.storage,
+                now,
+                &policy,
+                &config,
+                &withdraw_info,
+                denom,
+                amount,
+            )?;
+            resp = resp
+                .add_messages(msgs)
+                .add_attribute("amount", format!("{amount}{denom}"));
+            queued
+        };
 
-    let resp = Response::new()
-        .add_message(bank_msg)
-        .add_attribute("amount", amount.to_string());
+        if idx == 0 {
+            if let Some(data) = withdraw_data(&withdraw_info, denom, amount, queued)? {
+                resp = resp.set_data(data);
+            }
+        }
+    }
 
     Ok(resp)
 }
 
+// above the cap the withdrawal waits for the timelock and can be cancelled meanwhile
+// caps are in the group denom, other denoms can't be measured against them and always wait
+fn pay_or_queue(
+    storage: &mut dyn Storage,
+    now: u64,
+    policy: &WithdrawalPolicy,
+    config: &Config,
+    withdraw_info: &WithdrawalData,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<(Vec<BankMsg>, Option<u64>)> {
+    if let Some(cap) = policy.period_cap {
+        let mut period = PERIOD_WITHDRAWALS
+            .may_load(storage)?
+            .filter(|period| now < period.start + policy.period)
+            .unwrap_or(PeriodWithdrawals {
+                start: now,
+                amount: Uint128::zero(),
+            });
+
+        if denom != config.denom || period.amount + amount > cap {
+            let id = NEXT_WITHDRAWAL_ID.may_load(storage)?.unwrap_or_default();
+            NEXT_WITHDRAWAL_ID.save(storage, &(id + 1))?;
+
+            QUEUED_WITHDRAWALS.save(
+                storage,
+                id,
+                &PendingWithdrawal {
+                    receiver: withdraw_info.receiver.clone(),
+                    amount,
+                    denom: denom.to_owned(),
+                    release_at: now + policy.timelock,
+                },
+            )?;
+            return Ok((vec![], Some(id)));
+        }
+
+        period.amount += amount;
+        PERIOD_WITHDRAWALS.save(storage, &period)?;
+    }
+
+    // send token to receiver, or split it between collaborators
+    let payees = payees(storage, withdraw_info.receiver.clone())?;
+    Ok((payout(&payees, amount, denom), None))
+}
+
+// only explicit withdrawals report data, the one forwarding a donation keeps the donate data
+fn withdraw_data(
+    withdraw_info: &WithdrawalData,
+    denom: &str,
+    amount: Uint128,
+    queued: Option<u64>,
+) -> StdResult<Option<Binary>> {
+    withdraw_info
+        .weights
+        .map(|(old_weight, new_weight)| {
+            to_json_binary(&WithdrawData {
+                amount,
+                denom: denom.to_owned(),
+                receiver: withdraw_info.receiver.as_ref().map(Addr::to_string),
+                queued,
+                old_weight,
+                new_weight,
+            })
+        })
+        .transpose()
+}
+
 // forward data get from reply of membership contract
 pub fn propose_member(reply: Result<SubMsgResponse, String>) -> Result<Response, ContractError> {
     let response = reply.map_err(StdError::generic_err)?;
@@ -48,3 +167,53 @@ pub fn propose_member(reply: Result<SubMsgResponse, String>) -> Result<Response,
         Ok(Response::new())
     }
 }
+
+// only failures come here, donation goes through even if the receipt can not be minted
+pub fn receipt(reply: Result<SubMsgResponse, String>) -> Result<Response, ContractError> {
+    let resp = match reply {
+        Ok(_) => Response::new(),
+        Err(err) => Response::new()
+            .add_attribute("receipt", "failed")
+            .add_attribute("receipt_error", err),
+    };
+    Ok(resp)
+}
+
+// router sent the group denom back, the swapped donation is split as any other
+pub fn swapped(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let swap = PENDING_SWAP.load(deps.storage)?;
+    PENDING_SWAP.remove(deps.storage);
+
+    let config = CONFIG.load(deps.storage)?;
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &config.denom)?;
+    let amount = balance
+        .amount
+        .checked_sub(swap.balance)
+        .map_err(StdError::from)?;
+    // the router is not trusted to enforce the minimum on its own
+    if let Some(minimum) = swap.minimum_receive {
+        ensure!(
+            amount >= minimum,
+            ContractError::MinimumReceive {
+                minimum,
+                received: amount,
+            }
+        );
+    }
+
+    let donation_msgs = donation_msgs(deps.storage, &env, &config, &config.denom, amount, 1)?;
+    let receipt_msg = receipt_msg(deps.storage, &env, &swap.donor, amount, &config.denom)?;
+
+    let data = donate_data(deps.storage, &config, amount)?;
+
+    let resp = Response::new()
+        .add_submessages(donation_msgs)
+        .add_submessages(receipt_msg)
+        .set_data(to_json_binary(&data)?)
+        .add_attribute("swapped", swap.offer.to_string())
+        .add_attribute("amount", amount.to_string());
+
+    Ok(resp)
+}
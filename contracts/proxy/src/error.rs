@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use common::validate::ValidationError;
+use cosmwasm_std::{StdError, Uint128};
 use cw_utils::PaymentError;
 use thiserror::Error;
 
@@ -6,12 +7,61 @@ use thiserror::Error;
 pub enum ContractError {
     #[error("{0}")]
     StdError(#[from] StdError),
-    #[error("Direct part has to be between 0 and 1")]
-    InalidDirectPart,
     #[error("Unauthorized")]
     Unauthorized,
     #[error("{0}")]
     PaymentError(#[from] PaymentError),
+    #[error("{0}")]
+    Validation(#[from] ValidationError),
     #[error("Unrecognized reply id: {0}")]
     UnrecognizedReplyId(u64),
+    #[error("Campaign deadline has to be in the future")]
+    InvalidDeadline,
+    #[error("Campaign {0} is not active")]
+    CampaignNotActive(u64),
+    #[error("Campaign {0} is still running")]
+    CampaignInProgress(u64),
+    #[error("Campaign {0} did not fail")]
+    CampaignNotFailed(u64),
+    #[error("No pledge to reclaim")]
+    NoPledge,
+    #[error("Ownership transfer expiry has to be in the future")]
+    InvalidExpiry,
+    #[error("No pending ownership transfer")]
+    NoPendingOwner,
+    #[error("Ownership transfer expired")]
+    OwnershipTransferExpired,
+    #[error("Insufficient funds: requested {requested}, available {available}")]
+    InsufficientFunds {
+        requested: Uint128,
+        available: Uint128,
+    },
+    #[error("Denom {0} is not accepted by this proxy")]
+    UnknownDenom(String),
+    #[error("Denom {0} is the group denom")]
+    GroupDenom(String),
+    #[error("Swap returned {received}, below the minimum of {minimum}")]
+    MinimumReceive { minimum: Uint128, received: Uint128 },
+    #[error("Operator withdrawal cap exceeded")]
+    OperatorCapExceeded,
+    #[error("Receiver {0} is not allowed")]
+    ReceiverNotAllowed(String),
+    #[error("Withdrawal period has to be set together with the cap")]
+    InvalidWithdrawalPeriod,
+    #[error("Withdrawal {0} is still timelocked")]
+    WithdrawalLocked(u64),
+    #[error("Split shares have to be positive, unique and sum up to 1")]
+    InvalidSplit,
+    #[error("Paused")]
+    Paused,
+    #[error("Proxy is closed")]
+    Closed,
+    #[error("Invalid treasury part")]
+    InvalidTreasuryPart,
+    #[error("Swap max spread has to be between 0 and 1")]
+    InvalidMaxSpread,
+    #[error("Group shares have to be positive and sum up to at most 1")]
+    InvalidGroupShare,
+    #[error("Proxy is already a member of this group")]
+    AlreadyInGroup,
 }
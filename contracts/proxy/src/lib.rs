@@ -2,20 +2,22 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
 use error::ContractError;
-use msg::{ExecMsg, InstantiateMsg, QueryMsg};
+use msg::{ExecMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 mod contract;
-mod error;
+pub mod error;
 pub mod msg;
 #[cfg(any(feature = "mt", test))]
 pub mod multitest;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod schema;
 pub mod state;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     env: Env,
-    info: MessageInfo,
+    _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     contract::instantiate(deps, env, msg)
@@ -40,3 +42,8 @@ pub fn execute(
 pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, ContractError> {
     contract::reply(deps, env, reply)
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    contract::migrate(deps, msg)
+}
@@ -1,5 +1,9 @@
-use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, Uint128};
+use common::msg::{IbcDenom, PauseState, SpendMsg, SwapConfig};
+use common::validate::{self, Validate, ValidationError};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Coin, Decimal, Uint128};
+
+use crate::state::{CampaignStatus, Permission, WithdrawalPolicy};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -10,23 +14,241 @@ pub struct InstantiateMsg {
     pub distribution_contract: String,
     pub membership_contract: String,
     pub halftime: u64,
+    pub receipts: Option<ReceiptsConfig>, // None => no receipt NFTs for donors
+    pub treasury_part: Option<Decimal>,   // None => nothing goes to the group treasury
+    pub ibc_denoms: Option<Vec<IbcDenom>>, // accepted next to denom
+    pub swap: Option<SwapConfig>,         // None => other tokens are rejected
+}
+
+impl Validate for InstantiateMsg {
+    fn validate(&self) -> Result<(), ValidationError> {
+        validate::denom(&self.denom)?;
+        validate::direct_part(self.direct_part)?;
+        validate::halftime(self.halftime)
+    }
+}
+
+impl InstantiateMsg {
+    // optional parts are off by default
+    pub fn builder(
+        owner: &str,
+        denom: &str,
+        distribution_contract: &str,
+        membership_contract: &str,
+    ) -> InstantiateMsgBuilder {
+        InstantiateMsgBuilder(InstantiateMsg {
+            owner: owner.to_owned(),
+            weight: 10,
+            denom: denom.to_owned(),
+            direct_part: Decimal::percent(15),
+            distribution_contract: distribution_contract.to_owned(),
+            membership_contract: membership_contract.to_owned(),
+            halftime: 3600 * 24 * 30,
+            receipts: None,
+            treasury_part: None,
+            ibc_denoms: None,
+            swap: None,
+        })
+    }
+}
+
+pub struct InstantiateMsgBuilder(InstantiateMsg);
+
+impl InstantiateMsgBuilder {
+    pub fn weight(mut self, weight: u64) -> Self {
+        self.0.weight = weight;
+        self
+    }
+
+    pub fn direct_part(mut self, direct_part: Decimal) -> Self {
+        self.0.direct_part = direct_part;
+        self
+    }
+
+    pub fn halftime(mut self, halftime: u64) -> Self {
+        self.0.halftime = halftime;
+        self
+    }
+
+    pub fn receipts(mut self, receipts: ReceiptsConfig) -> Self {
+        self.0.receipts = Some(receipts);
+        self
+    }
+
+    pub fn treasury_part(mut self, treasury_part: Decimal) -> Self {
+        self.0.treasury_part = Some(treasury_part);
+        self
+    }
+
+    pub fn ibc_denoms(mut self, ibc_denoms: Vec<IbcDenom>) -> Self {
+        self.0.ibc_denoms = Some(ibc_denoms);
+        self
+    }
+
+    pub fn swap(mut self, swap: SwapConfig) -> Self {
+        self.0.swap = Some(swap);
+        self
+    }
+
+    pub fn build(self) -> Result<InstantiateMsg, ValidationError> {
+        self.0.validate()?;
+        Ok(self.0)
+    }
 }
 
 #[cw_serde]
-pub enum QueryMsg {}
+pub struct ReceiptsConfig {
+    pub contract: String,   // group-wide collection
+    pub threshold: Uint128, // minimal donation getting a receipt
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(CampaignResponse)]
+    Campaign { id: u64 },
+    #[returns(ListCampaignsResponse)]
+    ListCampaigns {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(ListOperatorsResponse)]
+    ListOperators {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(WithdrawalPolicyResponse)]
+    WithdrawalPolicy {},
+    #[returns(SplitResponse)]
+    Split {},
+    #[returns(AutoForwardResponse)]
+    AutoForward {},
+    #[returns(QueuedWithdrawalsResponse)]
+    QueuedWithdrawals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(GroupsResponse)]
+    Groups {},
+}
 
 #[cw_serde]
 pub enum ExecMsg {
-    Donate {},
+    Donate {
+        // when set, the donation is escrowed for this campaign instead of being split right away
+        campaign: Option<u64>,
+        // least amount of the group denom a swapped donation has to bring, the swap fails below it
+        minimum_receive: Option<Uint128>,
+    },
+    // None denom => the group denom with an amount, otherwise everything in every denom
     Withdraw {
         receiver: Option<String>,
         amount: Option<Uint128>,
+        denom: Option<String>,
     },
     Close {},
     ProposeMember {
         addr: String,
     },
     UpdateWeight {},
+    CreateCampaign {
+        title: String,
+        target: Uint128,
+        deadline: u64,
+    },
+    CloseCampaign {
+        id: u64,
+    },
+    // after a campaign failed, the donor takes the pledge back or turns it into a general donation
+    ReclaimPledge {
+        campaign: u64,
+        rollover: bool,
+    },
+    TransferOwnership {
+        new_owner: String,
+        expiry: Option<u64>,
+    },
+    AcceptOwnership {},
+    // operators act on behalf of the owner, limited to the granted permissions
+    GrantOperator {
+        operator: String,
+        permissions: Vec<Permission>,
+        expiry: Option<u64>,
+    },
+    RevokeOperator {
+        operator: String,
+    },
+    // takes effect after the timelock of the current policy
+    SetWithdrawalPolicy {
+        allowed_receivers: Option<Vec<String>>,
+        period_cap: Option<Uint128>,
+        period: u64,
+        timelock: u64,
+        guardian: Option<String>,
+    },
+    ExecuteWithdrawal {
+        id: u64,
+    },
+    CancelWithdrawal {
+        id: u64,
+    },
+    // empty list removes the split, withdrawals go to the owner again
+    UpdateSplit {
+        recipients: Vec<SplitRecipient>,
+    },
+    // None turns the auto forward off
+    SetAutoForward {
+        receiver: Option<String>,
+    },
+    // owner only, adds an accepted ibc denom or changes its origin chain
+    SetIbcDenom {
+        denom: String,
+        origin: String,
+    },
+    // owner only, the denom is neither accepted nor withdrawn any more, so withdraw it first
+    RemoveIbcDenom {
+        denom: String,
+    },
+    // only membership contract can set it
+    SetPaused {
+        paused: PauseState,
+    },
+    VoteUnpause {},
+    ProposeRemoval {
+        member: String,
+        reason: String,
+    },
+    // owner only, the proxy is closed the same way as after a removal
+    Leave {},
+    // only membership contract, after the group voted the owner out
+    Remove {},
+    // spending from the group treasury, forwarded to membership
+    ProposeSpend {
+        msgs: Vec<SpendMsg>,
+        description: String,
+    },
+    VoteSpend {
+        id: u64,
+    },
+    // owner only, members of the other group still have to accept this proxy
+    JoinGroup {
+        membership: String,
+        share: Decimal,
+    },
+    // sent by the membership contract of the joined group
+    GroupAccepted {
+        distribution_contract: String,
+    },
+}
+
+// nothing to convert so far, migrating only replaces the code
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+pub struct SplitRecipient {
+    pub addr: String,
+    pub share: Decimal,
 }
 
 #[cw_serde]
@@ -35,7 +257,128 @@ pub enum DistribtionExecMsg {
     Withdraw { weight: u64, diff: i64 },
 }
 
+// Astroport-style router, only native tokens are swapped
+#[cw_serde]
+pub enum AssetInfo {
+    NativeToken { denom: String },
+}
+
+#[cw_serde]
+pub enum SwapOperation {
+    AstroSwap {
+        offer_asset_info: AssetInfo,
+        ask_asset_info: AssetInfo,
+    },
+}
+
+#[cw_serde]
+pub enum RouterExecMsg {
+    ExecuteSwapOperations {
+        operations: Vec<SwapOperation>,
+        minimum_receive: Option<Uint128>,
+        to: Option<String>,
+        max_spread: Option<Decimal>,
+    },
+}
+
 #[cw_serde]
 pub enum MembershipExecMsg {
-    ProposeMember { addr: String },
+    ProposeMember {
+        addr: String,
+    },
+    OwnershipTransferred {
+        previous_owner: String,
+        new_owner: String,
+    },
+    ProposeUnpause {},
+    ProposeRemoval {
+        member: String,
+        reason: String,
+    },
+    Leave {},
+    WeightChanged {
+        weight: u64,
+    },
+    Donated {
+        amount: Coin,
+    },
+    Decayed {},
+    ProposeSpend {
+        msgs: Vec<SpendMsg>,
+        description: String,
+    },
+    VoteSpend {
+        id: u64,
+    },
+}
+
+#[cw_serde]
+pub struct CampaignResponse {
+    pub id: u64,
+    pub title: String,
+    pub target: Uint128,
+    pub deadline: u64,
+    pub raised: Uint128,
+    pub status: CampaignStatus,
+}
+
+#[cw_serde]
+pub struct ListCampaignsResponse {
+    pub campaigns: Vec<CampaignResponse>,
+}
+
+#[cw_serde]
+pub struct OperatorResponse {
+    pub addr: String,
+    pub permissions: Vec<Permission>,
+    pub expiry: Option<u64>,
+}
+
+#[cw_serde]
+pub struct ListOperatorsResponse {
+    pub operators: Vec<OperatorResponse>,
+}
+
+#[cw_serde]
+pub struct WithdrawalPolicyResponse {
+    pub policy: WithdrawalPolicy,
+    pub scheduled: Option<WithdrawalPolicy>,
+    pub scheduled_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct QueuedWithdrawalResponse {
+    pub id: u64,
+    pub receiver: Option<String>,
+    pub amount: Uint128,
+    pub denom: String,
+    pub release_at: u64,
+}
+
+#[cw_serde]
+pub struct QueuedWithdrawalsResponse {
+    pub withdrawals: Vec<QueuedWithdrawalResponse>,
+}
+
+#[cw_serde]
+pub struct SplitResponse {
+    pub recipients: Vec<SplitRecipient>,
+}
+
+#[cw_serde]
+pub struct GroupResponse {
+    pub membership: String,
+    pub distribution: String,
+    pub share: Decimal,
+}
+
+// home group goes first
+#[cw_serde]
+pub struct GroupsResponse {
+    pub groups: Vec<GroupResponse>,
+}
+
+#[cw_serde]
+pub struct AutoForwardResponse {
+    pub receiver: Option<String>,
 }
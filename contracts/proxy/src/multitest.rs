@@ -1,15 +1,33 @@
 use anyhow::Result as AnyResult;
-use common::msg::ProposeMemberData;
-use cosmwasm_std::{from_json, Addr, Coin, Decimal};
-use cw_multi_test::{App, ContractWrapper, Executor};
-use cw_utils::parse_execute_response_data;
+use common::msg::{
+    DistributionInstantiateMsg, DistributionQueryMsg, DonateData, PendingRewardsResponse,
+    ProposeMemberData, SpendMsg, UpdateWeightData, WithdrawData,
+};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Empty,
+    Env, MessageInfo, Response, StdError, StdResult, Uint128,
+};
+use cw_multi_test::{App, AppResponse, BankSudo, ContractWrapper, Executor, SudoMsg};
+use cw_storage_plus::Item;
+use cw_utils::{one_coin, parse_execute_response_data};
+use serde::de::DeserializeOwned;
 
 use crate::{
     execute, instantiate,
-    msg::{ExecMsg, InstantiateMsg, MembershipExecMsg},
+    msg::{
+        AssetInfo, AutoForwardResponse, CampaignResponse, DistribtionExecMsg, ExecMsg,
+        GroupsResponse, InstantiateMsg, ListCampaignsResponse, ListOperatorsResponse,
+        MembershipExecMsg, QueryMsg, QueuedWithdrawalsResponse, RouterExecMsg, SplitRecipient,
+        SplitResponse, SwapOperation, WithdrawalPolicyResponse,
+    },
     query, reply,
+    state::Permission,
 };
 
+#[cfg(test)]
+mod test;
+
 #[derive(Clone, Copy, Debug)]
 pub struct CodeId(u64);
 
@@ -22,33 +40,14 @@ impl CodeId {
       // remember need to declare all entry point in ContractWrapper
 
     #[track_caller]
-    pub fn instantiate(
+    pub fn instantiate_with(
         self,
         app: &mut App,
         sender: &str,
-        owner: &str,
-        weight: u64,
-        denom: &str,
-        direct_part: Decimal,
-        distribution_contract: &str,
-        membership_contract: &str,
-        halftime: u64,
+        msg: InstantiateMsg,
         label: &str,
     ) -> AnyResult<Contract> {
-        // this is static function?
-        Contract::instantiate(
-            app,
-            self,
-            sender,
-            owner,
-            weight,
-            denom,
-            direct_part,
-            distribution_contract,
-            membership_contract,
-            halftime,
-            label,
-        )
+        Contract::instantiate_with(app, self, sender, msg, label)
     }
 }
 
@@ -58,6 +57,232 @@ impl From<CodeId> for u64 {
     }
 }
 
+// there is no distribution contract yet, so tests use this stand-in
+// it keeps everything distributed to it and pays nothing back on withdraw, so nothing is pending
+pub fn store_distribution_mock(app: &mut App) -> u64 {
+    fn execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: DistribtionExecMsg,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: DistributionInstantiateMsg,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(_deps: Deps, _env: Env, msg: DistributionQueryMsg) -> StdResult<Binary> {
+        match msg {
+            DistributionQueryMsg::PendingRewards { .. } => {
+                to_json_binary(&PendingRewardsResponse {
+                    amount: Uint128::zero(),
+                })
+            }
+        }
+    }
+
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    app.store_code(Box::new(contract))
+}
+
+// pays out everything it holds in the denoms it was instantiated with on every withdraw,
+// as if the withdrawing proxy was the only member
+pub fn store_paying_distribution_mock(app: &mut App) -> u64 {
+    const DENOMS: Item<Vec<String>> = Item::new("denoms");
+
+    fn execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: DistribtionExecMsg,
+    ) -> StdResult<Response> {
+        let DistribtionExecMsg::Withdraw { .. } = msg else {
+            return Ok(Response::new());
+        };
+
+        let mut amount = vec![];
+        for denom in DENOMS.load(deps.storage)? {
+            let balance = deps.querier.query_balance(&env.contract.address, denom)?;
+            if !balance.amount.is_zero() {
+                amount.push(balance);
+            }
+        }
+        if amount.is_empty() {
+            return Ok(Response::new());
+        }
+
+        Ok(Response::new().add_message(BankMsg::Send {
+            to_address: info.sender.into_string(),
+            amount,
+        }))
+    }
+
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: DistributionInstantiateMsg,
+    ) -> StdResult<Response> {
+        let denoms = std::iter::once(msg.denom).chain(msg.ibc_denoms).collect();
+        DENOMS.save(deps.storage, &denoms)?;
+        Ok(Response::new())
+    }
+
+    // whatever it holds in the group denom goes to the next proxy withdrawing
+    fn query(deps: Deps, env: Env, msg: DistributionQueryMsg) -> StdResult<Binary> {
+        match msg {
+            DistributionQueryMsg::PendingRewards { .. } => {
+                let denom = &DENOMS.load(deps.storage)?[0];
+                let balance = deps.querier.query_balance(&env.contract.address, denom)?;
+                to_json_binary(&PendingRewardsResponse {
+                    amount: balance.amount,
+                })
+            }
+        }
+    }
+
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    app.store_code(Box::new(contract))
+}
+
+// proxies report to membership, standalone proxy tests only need somebody to accept it
+pub fn store_membership_mock(app: &mut App) -> u64 {
+    fn execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: MembershipExecMsg,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Ok(Binary::default())
+    }
+
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    app.store_code(Box::new(contract))
+}
+
+// Astroport-style router with a single constant product pool over its own balances,
+// it fails like the real one when the spread to the spot price is above max_spread
+pub fn store_router_mock(app: &mut App) -> u64 {
+    fn execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: RouterExecMsg,
+    ) -> StdResult<Response> {
+        let RouterExecMsg::ExecuteSwapOperations {
+            operations,
+            to,
+            max_spread,
+            ..
+        } = msg;
+        let Some(SwapOperation::AstroSwap {
+            ask_asset_info: AssetInfo::NativeToken { denom: ask_denom },
+            ..
+        }) = operations.last()
+        else {
+            return Err(StdError::generic_err("Must provide swap operations"));
+        };
+        let offer = one_coin(&info).map_err(|err| StdError::generic_err(err.to_string()))?;
+
+        // offered funds are already in, the pool is what was there before
+        let offer_pool = deps
+            .querier
+            .query_balance(&env.contract.address, &offer.denom)?
+            .amount
+            - offer.amount;
+        let ask_pool = deps
+            .querier
+            .query_balance(&env.contract.address, ask_denom)?
+            .amount;
+
+        let return_amount = ask_pool.multiply_ratio(offer.amount, offer_pool + offer.amount);
+        let spread = Decimal::from_ratio(offer.amount, offer_pool + offer.amount);
+        if max_spread.is_some_and(|max_spread| spread > max_spread) {
+            return Err(StdError::generic_err("Operation exceeds max spread limit"));
+        }
+
+        let receiver = to.unwrap_or_else(|| info.sender.to_string());
+        Ok(Response::new().add_message(BankMsg::Send {
+            to_address: receiver,
+            amount: coins(return_amount.u128(), ask_denom),
+        }))
+    }
+
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Ok(Binary::default())
+    }
+
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    app.store_code(Box::new(contract))
+}
+
+// memo of an ICS-20 transfer handled by the ibc-hooks module
+#[cw_serde]
+pub struct IbcHooksMemo {
+    pub wasm: IbcHooksWasm,
+}
+
+#[cw_serde]
+pub struct IbcHooksWasm {
+    pub contract: String,
+    pub msg: ExecMsg,
+}
+
+// multitest has no IBC, this plays the ibc-hooks module receiving a transfer over channel:
+// the voucher is minted to the intermediary account of the remote sender which executes the memo
+#[track_caller]
+pub fn ibc_hooks_transfer(
+    app: &mut App,
+    channel: &str,
+    sender: &str,
+    funds: Coin,
+    memo: &str,
+) -> AnyResult<AppResponse> {
+    let memo: IbcHooksMemo = from_json(memo.as_bytes())?;
+    let intermediary = Addr::unchecked(format!("ibc-hooks/{channel}/{sender}"));
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: intermediary.to_string(),
+        amount: vec![funds.clone()],
+    }))?;
+
+    app.execute_contract(
+        intermediary,
+        Addr::unchecked(memo.wasm.contract),
+        &memo.wasm.msg,
+        &[funds],
+    )
+}
+
 // tuple struct
 #[derive(Debug)]
 pub struct Contract(Addr);
@@ -73,54 +298,476 @@ impl Contract {
         &self.0
     }
 
+    // msg usually comes from InstantiateMsg::builder
     #[track_caller]
-    pub fn instantiate(
+    pub fn instantiate_with(
         app: &mut App,
         code_id: CodeId,
         sender: &str,
-        owner: &str,
-        weight: u64,
-        denom: &str,
-        direct_part: Decimal,
-        distribution_contract: &str,
-        membership_contract: &str,
-        halftime: u64,
+        msg: InstantiateMsg,
         label: &str,
     ) -> AnyResult<Self> {
-        let init_msg = InstantiateMsg {
-            owner: owner.to_owned(),
-            weight,
-            denom: denom.to_owned(),
-            direct_part,
-            distribution_contract: distribution_contract.to_owned(),
-            membership_contract: membership_contract.to_owned(),
-            halftime,
+        app.instantiate_contract(code_id.0, Addr::unchecked(sender), &msg, &[], label, None)
+            .map(Self) // not understand this => need to re-watch the video of previous course
+                       // i think .map will create a new instance of Self => and return it
+                       // Self in this case is a tuple struct
+                       // self is instance of Self
+                       // .map take closure
+                       // |contract_addr| Contract(contract_addr)
+    }
+
+    #[track_caller]
+    pub fn donate(
+        &self,
+        app: &mut App,
+        sender: &str,
+        funds: &[Coin],
+    ) -> AnyResult<Option<DonateData>> {
+        let donate_msg = ExecMsg::Donate {
+            campaign: None,
+            minimum_receive: None,
         };
+        let resp =
+            app.execute_contract(Addr::unchecked(sender), self.0.clone(), &donate_msg, funds)?;
 
-        app.instantiate_contract(
-            code_id.0,
-            Addr::unchecked(sender),
-            &init_msg,
-            &[],
-            label,
-            None,
-        )
-        .map(Self) // not understand this => need to re-watch the video of previous course
-                   // i think .map will create a new instance of Self => and return it
-                   // Self in this case is a tuple struct
-                   // self is instance of Self
-                   // .map take closure
-                   // |contract_addr| Contract(contract_addr)
+        parse_data(&resp)
+    }
+
+    #[track_caller]
+    pub fn donate_with_minimum(
+        &self,
+        app: &mut App,
+        sender: &str,
+        funds: &[Coin],
+        minimum_receive: u128,
+    ) -> AnyResult<Option<DonateData>> {
+        let donate_msg = ExecMsg::Donate {
+            campaign: None,
+            minimum_receive: Some(Uint128::new(minimum_receive)),
+        };
+        let resp =
+            app.execute_contract(Addr::unchecked(sender), self.0.clone(), &donate_msg, funds)?;
+
+        parse_data(&resp)
     }
 
     #[track_caller]
-    pub fn donate(&self, app: &mut App, sender: &str, funds: &[Coin]) -> AnyResult<()> {
-        let donate_msg = ExecMsg::Donate {};
+    pub fn donate_to_campaign(
+        &self,
+        app: &mut App,
+        sender: &str,
+        campaign: u64,
+        funds: &[Coin],
+    ) -> AnyResult<()> {
+        let donate_msg = ExecMsg::Donate {
+            campaign: Some(campaign),
+            minimum_receive: None,
+        };
         app.execute_contract(Addr::unchecked(sender), self.0.clone(), &donate_msg, funds)?;
 
         Ok(())
     }
 
+    #[track_caller]
+    pub fn create_campaign(
+        &self,
+        app: &mut App,
+        sender: &str,
+        title: &str,
+        target: u128,
+        deadline: u64,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::CreateCampaign {
+            title: title.to_owned(),
+            target: Uint128::new(target),
+            deadline,
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn close_campaign(&self, app: &mut App, sender: &str, id: u64) -> AnyResult<()> {
+        let msg = ExecMsg::CloseCampaign { id };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn reclaim_pledge(
+        &self,
+        app: &mut App,
+        sender: &str,
+        campaign: u64,
+        rollover: bool,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::ReclaimPledge { campaign, rollover };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn transfer_ownership(
+        &self,
+        app: &mut App,
+        sender: &str,
+        new_owner: &str,
+        expiry: Option<u64>,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::TransferOwnership {
+            new_owner: new_owner.to_owned(),
+            expiry,
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn accept_ownership(&self, app: &mut App, sender: &str) -> AnyResult<()> {
+        let msg = ExecMsg::AcceptOwnership {};
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn withdraw(
+        &self,
+        app: &mut App,
+        sender: &str,
+        receiver: Option<&str>,
+        amount: Option<u128>,
+    ) -> AnyResult<Option<WithdrawData>> {
+        let msg = ExecMsg::Withdraw {
+            receiver: receiver.map(str::to_owned),
+            amount: amount.map(Uint128::new),
+            denom: None,
+        };
+        let resp = app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        parse_data(&resp)
+    }
+
+    #[track_caller]
+    pub fn withdraw_denom(
+        &self,
+        app: &mut App,
+        sender: &str,
+        denom: &str,
+        amount: Option<u128>,
+    ) -> AnyResult<Option<WithdrawData>> {
+        let msg = ExecMsg::Withdraw {
+            receiver: None,
+            amount: amount.map(Uint128::new),
+            denom: Some(denom.to_owned()),
+        };
+        let resp = app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        parse_data(&resp)
+    }
+
+    #[track_caller]
+    pub fn close(&self, app: &mut App, sender: &str) -> AnyResult<()> {
+        let msg = ExecMsg::Close {};
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn grant_operator(
+        &self,
+        app: &mut App,
+        sender: &str,
+        operator: &str,
+        permissions: Vec<Permission>,
+        expiry: Option<u64>,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::GrantOperator {
+            operator: operator.to_owned(),
+            permissions,
+            expiry,
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn revoke_operator(&self, app: &mut App, sender: &str, operator: &str) -> AnyResult<()> {
+        let msg = ExecMsg::RevokeOperator {
+            operator: operator.to_owned(),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_withdrawal_policy(
+        &self,
+        app: &mut App,
+        sender: &str,
+        allowed_receivers: Option<&[&str]>,
+        period_cap: Option<u128>,
+        period: u64,
+        timelock: u64,
+        guardian: Option<&str>,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::SetWithdrawalPolicy {
+            allowed_receivers: allowed_receivers
+                .map(|receivers| receivers.iter().map(|addr| addr.to_string()).collect()),
+            period_cap: period_cap.map(Uint128::new),
+            period,
+            timelock,
+            guardian: guardian.map(str::to_owned),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn execute_withdrawal(&self, app: &mut App, sender: &str, id: u64) -> AnyResult<()> {
+        let msg = ExecMsg::ExecuteWithdrawal { id };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn cancel_withdrawal(&self, app: &mut App, sender: &str, id: u64) -> AnyResult<()> {
+        let msg = ExecMsg::CancelWithdrawal { id };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn update_split(
+        &self,
+        app: &mut App,
+        sender: &str,
+        recipients: &[(&str, Decimal)],
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::UpdateSplit {
+            recipients: recipients
+                .iter()
+                .map(|(addr, share)| SplitRecipient {
+                    addr: addr.to_string(),
+                    share: *share,
+                })
+                .collect(),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn set_auto_forward(
+        &self,
+        app: &mut App,
+        sender: &str,
+        receiver: Option<&str>,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::SetAutoForward {
+            receiver: receiver.map(str::to_owned),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn set_ibc_denom(
+        &self,
+        app: &mut App,
+        sender: &str,
+        denom: &str,
+        origin: &str,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::SetIbcDenom {
+            denom: denom.to_owned(),
+            origin: origin.to_owned(),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn remove_ibc_denom(&self, app: &mut App, sender: &str, denom: &str) -> AnyResult<()> {
+        let msg = ExecMsg::RemoveIbcDenom {
+            denom: denom.to_owned(),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn vote_unpause(&self, app: &mut App, sender: &str) -> AnyResult<()> {
+        let msg = ExecMsg::VoteUnpause {};
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn propose_removal(
+        &self,
+        app: &mut App,
+        sender: &str,
+        member: &str,
+        reason: &str,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::ProposeRemoval {
+            member: member.to_owned(),
+            reason: reason.to_owned(),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn leave(&self, app: &mut App, sender: &str) -> AnyResult<()> {
+        let msg = ExecMsg::Leave {};
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn propose_spend(
+        &self,
+        app: &mut App,
+        sender: &str,
+        msgs: Vec<SpendMsg>,
+        description: &str,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::ProposeSpend {
+            msgs,
+            description: description.to_owned(),
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn vote_spend(&self, app: &mut App, sender: &str, id: u64) -> AnyResult<()> {
+        let msg = ExecMsg::VoteSpend { id };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn update_weight(
+        &self,
+        app: &mut App,
+        sender: &str,
+    ) -> AnyResult<Option<UpdateWeightData>> {
+        let msg = ExecMsg::UpdateWeight {};
+        let resp = app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        parse_data(&resp)
+    }
+
+    pub fn auto_forward(&self, app: &App) -> AnyResult<AutoForwardResponse> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::AutoForward {})?;
+        Ok(resp)
+    }
+
+    #[track_caller]
+    pub fn join_group(
+        &self,
+        app: &mut App,
+        sender: &str,
+        membership: &str,
+        share: Decimal,
+    ) -> AnyResult<()> {
+        let msg = ExecMsg::JoinGroup {
+            membership: membership.to_owned(),
+            share,
+        };
+        app.execute_contract(Addr::unchecked(sender), self.0.clone(), &msg, &[])?;
+
+        Ok(())
+    }
+
+    pub fn groups(&self, app: &App) -> AnyResult<GroupsResponse> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Groups {})?;
+        Ok(resp)
+    }
+
+    pub fn split(&self, app: &App) -> AnyResult<SplitResponse> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Split {})?;
+        Ok(resp)
+    }
+
+    pub fn withdrawal_policy(&self, app: &App) -> AnyResult<WithdrawalPolicyResponse> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::WithdrawalPolicy {})?;
+        Ok(resp)
+    }
+
+    pub fn queued_withdrawals(&self, app: &App) -> AnyResult<QueuedWithdrawalsResponse> {
+        let resp = app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::QueuedWithdrawals {
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(resp)
+    }
+
+    pub fn list_operators(&self, app: &App) -> AnyResult<ListOperatorsResponse> {
+        let resp = app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::ListOperators {
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(resp)
+    }
+
+    pub fn campaign(&self, app: &App, id: u64) -> AnyResult<CampaignResponse> {
+        let resp = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Campaign { id })?;
+        Ok(resp)
+    }
+
+    pub fn list_campaigns(
+        &self,
+        app: &App,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> AnyResult<ListCampaignsResponse> {
+        let resp = app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::ListCampaigns { start_after, limit },
+        )?;
+        Ok(resp)
+    }
+
     #[track_caller]
     pub fn propose_member(
         &self,
@@ -139,7 +786,7 @@ impl Contract {
         )?;
 
         // response from the reply handler (fn propose_member) of proxy contract
-        // not understand
+        // it forwards the membership response as is, still wrapped as an execute response
         resp.data
             .map(|data| parse_execute_response_data(&data))
             .transpose()?
@@ -149,3 +796,12 @@ impl Contract {
             .map_err(Into::into)
     }
 }
+
+// data set by the executed contract, either directly or by its reply handlers
+pub fn parse_data<T: DeserializeOwned>(resp: &AppResponse) -> AnyResult<Option<T>> {
+    resp.data
+        .as_ref()
+        .map(from_json)
+        .transpose()
+        .map_err(Into::into)
+}
@@ -0,0 +1,977 @@
+use common::msg::{
+    DistributionInstantiateMsg, DonateData, IbcDenom, PausableAction, PauseState, SwapConfig,
+    UpdateWeightData, WithdrawData,
+};
+use common::validate::ValidationError;
+use cosmwasm_std::{coin, coins, Addr, Decimal, Empty, Uint128};
+use cw_multi_test::{App, BankSudo, Executor, SudoMsg};
+
+use crate::error::ContractError;
+use crate::msg::{ExecMsg, InstantiateMsg, ReceiptsConfig};
+use crate::multitest::{
+    ibc_hooks_transfer, store_distribution_mock, store_membership_mock,
+    store_paying_distribution_mock, store_router_mock, CodeId as ProxyId,
+    Contract as ProxyContract,
+};
+use crate::state::{CampaignStatus, Permission, DONATIONS, LAST_UPDATED, WEIGHT};
+
+const DENOM: &str = "ORAI";
+const OWNER: &str = "owner";
+const ATOM: &str = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+
+// blockchain with funded donors, membership and distribution stand-ins and a single proxy
+fn setup(donors: &[&str]) -> (App, ProxyContract, Addr) {
+    setup_with(donors, store_distribution_mock)
+}
+
+fn setup_with(
+    donors: &[&str],
+    store_distribution: fn(&mut App) -> u64,
+) -> (App, ProxyContract, Addr) {
+    let mut app = App::new(|router, _api, storage| {
+        for donor in donors {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked(*donor), coins(100, DENOM))
+                .unwrap();
+        }
+    });
+
+    let membership_code_id = store_membership_mock(&mut app);
+    let membership = app
+        .instantiate_contract(
+            membership_code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "Membership",
+            None,
+        )
+        .unwrap();
+
+    let distribution_code_id = store_distribution(&mut app);
+    let distribution = app
+        .instantiate_contract(
+            distribution_code_id,
+            Addr::unchecked(OWNER),
+            &DistributionInstantiateMsg {
+                denom: DENOM.to_owned(),
+                ibc_denoms: vec![ATOM.to_owned()],
+                membership_contract: membership.to_string(),
+            },
+            &[],
+            "Distribution",
+            None,
+        )
+        .unwrap();
+
+    let proxy = ProxyId::store_code(&mut app)
+        .instantiate_with(
+            &mut app,
+            OWNER,
+            InstantiateMsg::builder(OWNER, DENOM, distribution.as_str(), membership.as_str())
+                .ibc_denoms(vec![IbcDenom {
+                    denom: ATOM.to_owned(),
+                    origin: "cosmoshub-4".to_owned(),
+                }])
+                .build()
+                .unwrap(),
+            "Proxy",
+        )
+        .unwrap();
+
+    (app, proxy, distribution)
+}
+
+fn balance(app: &App, addr: &Addr) -> u128 {
+    app.wrap().query_balance(addr, DENOM).unwrap().amount.u128()
+}
+
+#[test]
+pub fn campaign_reaching_target() {
+    let (mut app, proxy, distribution) = setup(&["donor1", "donor2"]);
+    let deadline = app.block_info().time.seconds() + 3600;
+
+    // only the creator can start a campaign
+    proxy
+        .create_campaign(&mut app, "donor1", "New microphone", 150, deadline)
+        .unwrap_err();
+    proxy
+        .create_campaign(&mut app, OWNER, "New microphone", 150, deadline)
+        .unwrap();
+
+    proxy
+        .donate_to_campaign(&mut app, "donor1", 0, &coins(100, DENOM))
+        .unwrap();
+
+    // pledges are escrowed by the proxy until the campaign is closed
+    assert_eq!(balance(&app, proxy.addr()), 100);
+    assert_eq!(balance(&app, &distribution), 0);
+
+    // not reached and deadline not passed yet
+    proxy.close_campaign(&mut app, "donor1", 0).unwrap_err();
+
+    proxy
+        .donate_to_campaign(&mut app, "donor2", 0, &coins(50, DENOM))
+        .unwrap();
+    proxy.close_campaign(&mut app, "donor1", 0).unwrap();
+
+    let campaign = proxy.campaign(&app, 0).unwrap();
+    assert_eq!(campaign.raised.u128(), 150);
+    assert_eq!(campaign.status, CampaignStatus::Succeeded);
+
+    // raised amount went through the usual split
+    assert_eq!(balance(&app, proxy.addr()), 22);
+    assert_eq!(balance(&app, &distribution), 128);
+
+    // closed campaign doesn't take pledges anymore
+    proxy
+        .donate_to_campaign(&mut app, "donor2", 0, &coins(50, DENOM))
+        .unwrap_err();
+}
+
+#[test]
+pub fn campaign_missing_deadline() {
+    let (mut app, proxy, distribution) = setup(&["donor1", "donor2", "donor3"]);
+    let deadline = app.block_info().time.seconds() + 3600;
+
+    proxy
+        .create_campaign(&mut app, OWNER, "New microphone", 500, deadline)
+        .unwrap();
+    proxy
+        .create_campaign(&mut app, OWNER, "New camera", 1000, deadline)
+        .unwrap();
+
+    proxy
+        .donate_to_campaign(&mut app, "donor1", 0, &coins(40, DENOM))
+        .unwrap();
+    proxy
+        .donate_to_campaign(&mut app, "donor2", 0, &coins(20, DENOM))
+        .unwrap();
+
+    // escrowed pledges are not withdrawable by the owner, only the direct part of donations
+    proxy.donate(&mut app, "donor3", &coins(20, DENOM)).unwrap();
+    let err = proxy.withdraw(&mut app, OWNER, None, Some(4)).unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::InsufficientFunds { requested, available }
+            if requested.u128() == 4 && available.u128() == 3
+    ));
+    proxy.withdraw(&mut app, OWNER, None, None).unwrap();
+    assert_eq!(balance(&app, proxy.addr()), 60);
+    assert_eq!(balance(&app, &Addr::unchecked(OWNER)), 3);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+
+    // deadline passed, no more pledges
+    proxy
+        .donate_to_campaign(&mut app, "donor1", 0, &coins(10, DENOM))
+        .unwrap_err();
+
+    // nothing to reclaim before the campaign is closed as failed
+    proxy
+        .reclaim_pledge(&mut app, "donor1", 0, false)
+        .unwrap_err();
+
+    proxy.close_campaign(&mut app, "donor1", 0).unwrap();
+    assert_eq!(
+        proxy.campaign(&app, 0).unwrap().status,
+        CampaignStatus::Failed
+    );
+
+    // donor1 takes the pledge back, donor2 rolls it over into a general donation
+    proxy.reclaim_pledge(&mut app, "donor1", 0, false).unwrap();
+    proxy.reclaim_pledge(&mut app, "donor2", 0, true).unwrap();
+    proxy
+        .reclaim_pledge(&mut app, "donor1", 0, false)
+        .unwrap_err();
+
+    assert_eq!(balance(&app, &Addr::unchecked("donor1")), 100);
+    assert_eq!(balance(&app, &Addr::unchecked("donor2")), 80);
+    assert_eq!(balance(&app, proxy.addr()), 3);
+    assert_eq!(balance(&app, &distribution), 34);
+
+    let campaigns = proxy.list_campaigns(&app, None, None).unwrap().campaigns;
+    assert_eq!(campaigns.len(), 2);
+    assert_eq!(campaigns[1].title, "New camera");
+    assert_eq!(campaigns[1].status, CampaignStatus::Active);
+
+    let campaigns = proxy.list_campaigns(&app, Some(0), None).unwrap().campaigns;
+    assert_eq!(campaigns.len(), 1);
+    assert_eq!(campaigns[0].id, 1);
+}
+
+#[test]
+pub fn operators() {
+    let (mut app, proxy, _) = setup(&["donor"]);
+
+    proxy.donate(&mut app, "donor", &coins(100, DENOM)).unwrap();
+    assert_eq!(balance(&app, proxy.addr()), 15);
+
+    // only owner grants operators
+    proxy
+        .grant_operator(
+            &mut app,
+            "manager",
+            "manager",
+            vec![Permission::Close],
+            None,
+        )
+        .unwrap_err();
+
+    proxy
+        .grant_operator(&mut app, OWNER, "manager", vec![Permission::Vote], None)
+        .unwrap();
+    proxy
+        .grant_operator(
+            &mut app,
+            OWNER,
+            "hot_wallet",
+            vec![Permission::Withdraw {
+                cap: Some(Uint128::new(10)),
+            }],
+            None,
+        )
+        .unwrap();
+
+    // voting operator can not touch the funds nor close the proxy
+    proxy
+        .withdraw(&mut app, "manager", None, Some(5))
+        .unwrap_err();
+    proxy.close(&mut app, "manager").unwrap_err();
+
+    // capped operator has to stay under the cap, funds go to the owner by default
+    proxy
+        .withdraw(&mut app, "hot_wallet", None, Some(11))
+        .unwrap_err();
+    proxy
+        .withdraw(&mut app, "hot_wallet", None, None)
+        .unwrap_err();
+    proxy
+        .withdraw(&mut app, "hot_wallet", None, Some(10))
+        .unwrap();
+    assert_eq!(balance(&app, &Addr::unchecked(OWNER)), 10);
+    assert_eq!(balance(&app, proxy.addr()), 5);
+
+    // the cap counts everything withdrawn by the operator, not single calls
+    let err = proxy
+        .withdraw(&mut app, "hot_wallet", None, Some(1))
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::OperatorCapExceeded
+    ));
+
+    // operator pays out only to the owner or the split payees
+    proxy
+        .grant_operator(
+            &mut app,
+            OWNER,
+            "hot_wallet",
+            vec![Permission::Withdraw {
+                cap: Some(Uint128::new(10)),
+            }],
+            None,
+        )
+        .unwrap();
+    let err = proxy
+        .withdraw(&mut app, "hot_wallet", Some("thief"), Some(1))
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::ReceiverNotAllowed(receiver) if receiver == "thief"
+    ));
+    proxy
+        .withdraw(&mut app, "hot_wallet", Some(OWNER), Some(1))
+        .unwrap();
+    assert_eq!(balance(&app, &Addr::unchecked(OWNER)), 11);
+
+    let operators = proxy.list_operators(&app).unwrap().operators;
+    assert_eq!(operators.len(), 2);
+    assert_eq!(operators[0].addr, "hot_wallet");
+    assert_eq!(operators[1].permissions, vec![Permission::Vote]);
+
+    proxy
+        .revoke_operator(&mut app, OWNER, "hot_wallet")
+        .unwrap();
+    proxy
+        .withdraw(&mut app, "hot_wallet", None, Some(1))
+        .unwrap_err();
+
+    // expired operator loses his permissions
+    let expiry = app.block_info().time.seconds() + 60;
+    proxy
+        .grant_operator(
+            &mut app,
+            OWNER,
+            "manager",
+            vec![Permission::Close],
+            Some(expiry),
+        )
+        .unwrap();
+    app.update_block(|block| block.time = block.time.plus_seconds(60));
+    proxy.close(&mut app, "manager").unwrap_err();
+
+    assert_eq!(proxy.list_operators(&app).unwrap().operators.len(), 1);
+}
+
+#[test]
+pub fn withdrawal_limits() {
+    let (mut app, proxy, _) = setup(&["donor"]);
+    let cold_wallet = Addr::unchecked("cold_wallet");
+
+    proxy.donate(&mut app, "donor", &coins(100, DENOM)).unwrap();
+    assert_eq!(balance(&app, proxy.addr()), 15);
+
+    // without a timelock the first policy applies at once
+    proxy
+        .set_withdrawal_policy(
+            &mut app,
+            OWNER,
+            Some(&[cold_wallet.as_str()]),
+            Some(8),
+            3600 * 24,
+            3600,
+            Some("team"),
+        )
+        .unwrap();
+
+    // owner itself is not on the allowlist
+    proxy.withdraw(&mut app, OWNER, None, Some(5)).unwrap_err();
+
+    proxy
+        .withdraw(&mut app, OWNER, Some(cold_wallet.as_str()), Some(5))
+        .unwrap();
+    assert_eq!(balance(&app, &cold_wallet), 5);
+
+    // over the cap for this period => queued
+    proxy
+        .withdraw(&mut app, OWNER, Some(cold_wallet.as_str()), Some(4))
+        .unwrap();
+    assert_eq!(balance(&app, &cold_wallet), 5);
+
+    let queued = proxy.queued_withdrawals(&app).unwrap().withdrawals;
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].amount.u128(), 4);
+
+    proxy
+        .execute_withdrawal(&mut app, "anyone", queued[0].id)
+        .unwrap_err();
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    proxy
+        .execute_withdrawal(&mut app, "anyone", queued[0].id)
+        .unwrap();
+    assert_eq!(balance(&app, &cold_wallet), 9);
+
+    // queued withdrawal can be cancelled by the guardian
+    proxy
+        .withdraw(&mut app, OWNER, Some(cold_wallet.as_str()), Some(6))
+        .unwrap();
+    let id = proxy.queued_withdrawals(&app).unwrap().withdrawals[0].id;
+    proxy.cancel_withdrawal(&mut app, "anyone", id).unwrap_err();
+    proxy.cancel_withdrawal(&mut app, "team", id).unwrap();
+    assert!(proxy
+        .queued_withdrawals(&app)
+        .unwrap()
+        .withdrawals
+        .is_empty());
+    assert_eq!(balance(&app, proxy.addr()), 6);
+
+    // lifting the limits waits for the timelock
+    proxy
+        .set_withdrawal_policy(&mut app, OWNER, None, None, 0, 0, None)
+        .unwrap();
+    let policy = proxy.withdrawal_policy(&app).unwrap();
+    assert_eq!(policy.policy.period_cap, Some(Uint128::new(8)));
+    assert!(policy.scheduled.is_some());
+    proxy.withdraw(&mut app, OWNER, None, None).unwrap_err();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    proxy.withdraw(&mut app, OWNER, None, None).unwrap();
+    assert_eq!(balance(&app, &Addr::unchecked(OWNER)), 6);
+}
+
+#[test]
+pub fn revenue_split() {
+    let (mut app, proxy, _) = setup(&["donor"]);
+
+    proxy.donate(&mut app, "donor", &coins(100, DENOM)).unwrap();
+
+    // shares have to sum up to 1 and can't repeat
+    proxy
+        .update_split(
+            &mut app,
+            OWNER,
+            &[
+                ("editor", Decimal::percent(50)),
+                (OWNER, Decimal::percent(40)),
+            ],
+        )
+        .unwrap_err();
+    proxy
+        .update_split(
+            &mut app,
+            OWNER,
+            &[
+                ("editor", Decimal::percent(50)),
+                ("editor", Decimal::percent(50)),
+            ],
+        )
+        .unwrap_err();
+    proxy
+        .update_split(&mut app, "editor", &[("editor", Decimal::one())])
+        .unwrap_err();
+
+    proxy
+        .update_split(
+            &mut app,
+            OWNER,
+            &[
+                ("editor", Decimal::percent(50)),
+                ("cohost", Decimal::percent(30)),
+                (OWNER, Decimal::percent(20)),
+            ],
+        )
+        .unwrap();
+    assert_eq!(proxy.split(&app).unwrap().recipients.len(), 3);
+
+    // 15 direct part split, rounding leftover goes to the last recipient
+    proxy.withdraw(&mut app, OWNER, None, None).unwrap();
+    assert_eq!(balance(&app, &Addr::unchecked("editor")), 7);
+    assert_eq!(balance(&app, &Addr::unchecked("cohost")), 4);
+    assert_eq!(balance(&app, &Addr::unchecked(OWNER)), 4);
+    assert_eq!(balance(&app, proxy.addr()), 0);
+
+    proxy.update_split(&mut app, OWNER, &[]).unwrap();
+    assert!(proxy.split(&app).unwrap().recipients.is_empty());
+}
+
+#[test]
+pub fn auto_forward() {
+    let (mut app, proxy, distribution) = setup(&["donor1", "donor2"]);
+    let cold_wallet = Addr::unchecked("cold_wallet");
+
+    proxy.donate(&mut app, "donor1", &coins(60, DENOM)).unwrap();
+    assert_eq!(balance(&app, proxy.addr()), 9);
+
+    // receiver has to pass the allowlist
+    proxy
+        .set_withdrawal_policy(&mut app, OWNER, Some(&["team"]), None, 0, 0, None)
+        .unwrap();
+    proxy
+        .set_auto_forward(&mut app, OWNER, Some(cold_wallet.as_str()))
+        .unwrap_err();
+    proxy
+        .set_withdrawal_policy(
+            &mut app,
+            OWNER,
+            Some(&[cold_wallet.as_str()]),
+            None,
+            0,
+            0,
+            None,
+        )
+        .unwrap();
+
+    proxy
+        .set_auto_forward(&mut app, "donor1", Some(cold_wallet.as_str()))
+        .unwrap_err();
+    proxy
+        .set_auto_forward(&mut app, OWNER, Some(cold_wallet.as_str()))
+        .unwrap();
+    assert_eq!(
+        proxy.auto_forward(&app).unwrap().receiver.as_deref(),
+        Some(cold_wallet.as_str())
+    );
+
+    // nothing stays in the proxy, earlier direct part is pulled along with the new one
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    proxy
+        .donate(&mut app, "donor2", &coins(100, DENOM))
+        .unwrap();
+    assert_eq!(balance(&app, proxy.addr()), 0);
+    assert_eq!(balance(&app, &cold_wallet), 24);
+    assert_eq!(balance(&app, &distribution), 136);
+
+    // weight is updated as if the creator withdrew right after the donation
+    let weight = WEIGHT.query(&app.wrap(), proxy.addr().clone()).unwrap();
+    let donations = DONATIONS.query(&app.wrap(), proxy.addr().clone()).unwrap();
+    let last_updated = LAST_UPDATED
+        .query(&app.wrap(), proxy.addr().clone())
+        .unwrap();
+    assert_eq!(weight, 2);
+    assert_eq!(donations, 1);
+    assert_eq!(last_updated, app.block_info().time.seconds());
+
+    // revenue split takes precedence over the forward receiver
+    proxy
+        .update_split(
+            &mut app,
+            OWNER,
+            &[
+                (cold_wallet.as_str(), Decimal::percent(50)),
+                ("team", Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+    proxy.donate(&mut app, "donor1", &coins(20, DENOM)).unwrap();
+    assert_eq!(balance(&app, proxy.addr()), 0);
+    assert_eq!(balance(&app, &cold_wallet), 25);
+    assert_eq!(balance(&app, &Addr::unchecked("team")), 2);
+    proxy.update_split(&mut app, OWNER, &[]).unwrap();
+
+    // forwarded direct part is a withdrawal like any other, above the cap it waits for the timelock
+    proxy
+        .set_withdrawal_policy(
+            &mut app,
+            OWNER,
+            Some(&[cold_wallet.as_str()]),
+            Some(1),
+            3600,
+            3600,
+            None,
+        )
+        .unwrap();
+    proxy.donate(&mut app, "donor1", &coins(20, DENOM)).unwrap();
+    assert_eq!(balance(&app, proxy.addr()), 3);
+    assert_eq!(balance(&app, &cold_wallet), 25);
+
+    proxy.set_auto_forward(&mut app, OWNER, None).unwrap();
+    assert_eq!(proxy.auto_forward(&app).unwrap().receiver, None);
+}
+
+#[test]
+pub fn auto_forward_while_withdraw_paused() {
+    let (mut app, proxy, distribution) = setup(&["donor"]);
+    let membership = proxy.groups(&app).unwrap().groups[0].membership.clone();
+    let cold_wallet = Addr::unchecked("cold_wallet");
+
+    proxy
+        .set_auto_forward(&mut app, OWNER, Some(cold_wallet.as_str()))
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(membership),
+        proxy.addr().clone(),
+        &ExecMsg::SetPaused {
+            paused: PauseState {
+                all: false,
+                actions: vec![PausableAction::Withdraw],
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // forwarding would be a withdrawal, so the donation is refused and nothing is paid out
+    let err = proxy
+        .donate(&mut app, "donor", &coins(100, DENOM))
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ContractError::Paused));
+    assert_eq!(balance(&app, &Addr::unchecked("donor")), 100);
+    assert_eq!(balance(&app, proxy.addr()), 0);
+    assert_eq!(balance(&app, &cold_wallet), 0);
+    assert_eq!(balance(&app, &distribution), 0);
+
+    // without the forward donations go through as usual and stay on the proxy
+    proxy.set_auto_forward(&mut app, OWNER, None).unwrap();
+    proxy.donate(&mut app, "donor", &coins(100, DENOM)).unwrap();
+    assert_eq!(balance(&app, proxy.addr()), 15);
+    assert_eq!(balance(&app, &cold_wallet), 0);
+}
+
+#[test]
+pub fn receipt_failure_keeps_donation() {
+    let (mut app, proxy, distribution) = setup(&["donor"]);
+    // donations are reported to the membership stand-in of the setup proxy
+    let membership = proxy.groups(&app).unwrap().groups[0].membership.clone();
+
+    // distribution stand-in does not understand cw721 mint, so every receipt fails
+    let proxy = ProxyId::store_code(&mut app)
+        .instantiate_with(
+            &mut app,
+            OWNER,
+            InstantiateMsg::builder(OWNER, DENOM, distribution.as_str(), &membership)
+                .receipts(ReceiptsConfig {
+                    contract: distribution.to_string(),
+                    threshold: Uint128::new(50),
+                })
+                .build()
+                .unwrap(),
+            "Proxy",
+        )
+        .unwrap();
+
+    proxy.donate(&mut app, "donor", &coins(100, DENOM)).unwrap();
+    assert_eq!(balance(&app, proxy.addr()), 15);
+    assert_eq!(balance(&app, &distribution), 85);
+}
+
+#[test]
+pub fn ibc_donation() {
+    let (mut app, proxy, distribution) = setup_with(&[], store_paying_distribution_mock);
+
+    let memo = format!(
+        r#"{{"wasm":{{"contract":"{}","msg":{{"donate":{{}}}}}}}}"#,
+        proxy.addr()
+    );
+    let resp = ibc_hooks_transfer(
+        &mut app,
+        "channel-0",
+        "cosmos1donor",
+        coin(100, ATOM),
+        &memo,
+    )
+    .unwrap();
+    assert!(resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "origin" && attr.value == "cosmoshub-4")));
+
+    // split the same way as the group denom
+    let atom_balance = |app: &App, addr: &Addr| app.wrap().query_balance(addr, ATOM).unwrap();
+    assert_eq!(atom_balance(&app, proxy.addr()).amount.u128(), 15);
+    assert_eq!(atom_balance(&app, &distribution).amount.u128(), 85);
+
+    // vouchers the group doesn't know are rejected
+    ibc_hooks_transfer(
+        &mut app,
+        "channel-1",
+        "osmo1donor",
+        coin(100, "ibc/UNKNOWN"),
+        &memo,
+    )
+    .unwrap_err();
+
+    // campaigns are raised in the group denom only
+    let deadline = app.block_info().time.seconds() + 3600;
+    proxy
+        .create_campaign(&mut app, OWNER, "New microphone", 150, deadline)
+        .unwrap();
+    let memo = format!(
+        r#"{{"wasm":{{"contract":"{}","msg":{{"donate":{{"campaign":0}}}}}}}}"#,
+        proxy.addr()
+    );
+    ibc_hooks_transfer(
+        &mut app,
+        "channel-0",
+        "cosmos1donor",
+        coin(100, ATOM),
+        &memo,
+    )
+    .unwrap_err();
+
+    // withdrawn per denom like the group denom, distribution pays its share out as well
+    let data = proxy
+        .withdraw_denom(&mut app, OWNER, ATOM, Some(40))
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.amount.u128(), 40);
+    assert_eq!(data.denom, ATOM);
+    assert_eq!(atom_balance(&app, proxy.addr()).amount.u128(), 60);
+    assert_eq!(
+        atom_balance(&app, &Addr::unchecked(OWNER)).amount.u128(),
+        40
+    );
+
+    let err = proxy
+        .withdraw_denom(&mut app, OWNER, ATOM, Some(61))
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::InsufficientFunds { available, .. } if available.u128() == 60
+    ));
+    let err = proxy
+        .withdraw_denom(&mut app, OWNER, "ibc/UNKNOWN", None)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::UnknownDenom(denom) if denom == "ibc/UNKNOWN"
+    ));
+
+    // cap is in the group denom, other denoms always wait for the timelock
+    proxy
+        .set_withdrawal_policy(&mut app, OWNER, None, Some(1000), 3600, 3600, None)
+        .unwrap();
+    proxy.withdraw(&mut app, OWNER, None, None).unwrap();
+    assert_eq!(atom_balance(&app, proxy.addr()).amount.u128(), 60);
+
+    let queued = proxy.queued_withdrawals(&app).unwrap().withdrawals;
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].amount.u128(), 60);
+    assert_eq!(queued[0].denom, ATOM);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    proxy
+        .execute_withdrawal(&mut app, "anyone", queued[0].id)
+        .unwrap();
+    assert_eq!(atom_balance(&app, proxy.addr()).amount.u128(), 0);
+    assert_eq!(
+        atom_balance(&app, &Addr::unchecked(OWNER)).amount.u128(),
+        100
+    );
+}
+
+#[test]
+pub fn managing_ibc_denoms() {
+    let (mut app, proxy, distribution) = setup_with(&[], store_paying_distribution_mock);
+    let osmo = "ibc/OSMO";
+    let memo = format!(
+        r#"{{"wasm":{{"contract":"{}","msg":{{"donate":{{}}}}}}}}"#,
+        proxy.addr()
+    );
+
+    // only the owner manages accepted denoms, the group denom stays apart
+    let err = proxy
+        .set_ibc_denom(&mut app, "donor", osmo, "osmosis-1")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Unauthorized
+    ));
+    let err = proxy
+        .set_ibc_denom(&mut app, OWNER, DENOM, "osmosis-1")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::GroupDenom(denom) if denom == DENOM
+    ));
+
+    ibc_hooks_transfer(&mut app, "channel-1", "osmo1donor", coin(100, osmo), &memo).unwrap_err();
+    proxy
+        .set_ibc_denom(&mut app, OWNER, osmo, "osmosis-1")
+        .unwrap();
+    let resp =
+        ibc_hooks_transfer(&mut app, "channel-1", "osmo1donor", coin(100, osmo), &memo).unwrap();
+    assert!(resp.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "origin" && attr.value == "osmosis-1")));
+    let osmo_balance = |app: &App, addr: &Addr| app.wrap().query_balance(addr, osmo).unwrap();
+    assert_eq!(osmo_balance(&app, proxy.addr()).amount.u128(), 15);
+    assert_eq!(osmo_balance(&app, &distribution).amount.u128(), 85);
+
+    // removed denom is neither accepted nor withdrawn any more
+    let err = proxy.remove_ibc_denom(&mut app, "donor", ATOM).unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Unauthorized
+    ));
+    proxy.remove_ibc_denom(&mut app, OWNER, ATOM).unwrap();
+    ibc_hooks_transfer(
+        &mut app,
+        "channel-0",
+        "cosmos1donor",
+        coin(100, ATOM),
+        &memo,
+    )
+    .unwrap_err();
+    let err = proxy
+        .withdraw_denom(&mut app, OWNER, ATOM, None)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::UnknownDenom(denom) if denom == ATOM
+    ));
+    let err = proxy.remove_ibc_denom(&mut app, OWNER, ATOM).unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::UnknownDenom(denom) if denom == ATOM
+    ));
+}
+
+#[test]
+pub fn swap_on_donate() {
+    let (mut app, proxy, distribution) = setup(&[]);
+    // donations are reported to the membership stand-in of the setup proxy
+    let membership = proxy.groups(&app).unwrap().groups[0].membership.clone();
+
+    let router_code_id = store_router_mock(&mut app);
+    let router = app
+        .instantiate_contract(
+            router_code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "Router",
+            None,
+        )
+        .unwrap();
+    // pool of 10_000 USDC against 20_000 ORAI, and a donor holding USDC only
+    for (addr, funds) in [
+        (
+            router.as_str(),
+            vec![coin(10_000, "USDC"), coin(20_000, DENOM)],
+        ),
+        ("donor", coins(1100, "USDC")),
+    ] {
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: addr.to_owned(),
+            amount: funds,
+        }))
+        .unwrap();
+    }
+
+    let proxy = ProxyId::store_code(&mut app)
+        .instantiate_with(
+            &mut app,
+            OWNER,
+            InstantiateMsg::builder(OWNER, DENOM, distribution.as_str(), &membership)
+                .swap(SwapConfig {
+                    router: router.to_string(),
+                    max_spread: Decimal::percent(5),
+                })
+                .build()
+                .unwrap(),
+            "Proxy",
+        )
+        .unwrap();
+
+    // 100 USDC is swapped to 198 ORAI, then split as usual
+    proxy
+        .donate(&mut app, "donor", &coins(100, "USDC"))
+        .unwrap();
+    assert_eq!(balance(&app, proxy.addr()), 29);
+    assert_eq!(balance(&app, &distribution), 169);
+    assert_eq!(
+        app.wrap()
+            .query_balance(proxy.addr(), "USDC")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+
+    // router pays 194 ORAI for the next 100 USDC, the donor asked for at least 195
+    let err = proxy
+        .donate_with_minimum(&mut app, "donor", &coins(100, "USDC"), 195)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::MinimumReceive { minimum, received }
+            if minimum.u128() == 195 && received.u128() == 194
+    ));
+    assert_eq!(balance(&app, proxy.addr()), 29);
+    assert_eq!(
+        app.wrap()
+            .query_balance("donor", "USDC")
+            .unwrap()
+            .amount
+            .u128(),
+        1000
+    );
+
+    // 1000 USDC would move the pool price by 9%, above the slippage limit
+    proxy
+        .donate(&mut app, "donor", &coins(1000, "USDC"))
+        .unwrap_err();
+    assert_eq!(
+        app.wrap()
+            .query_balance("donor", "USDC")
+            .unwrap()
+            .amount
+            .u128(),
+        1000
+    );
+
+    // campaigns are raised in the group denom only
+    let deadline = app.block_info().time.seconds() + 3600;
+    proxy
+        .create_campaign(&mut app, OWNER, "New microphone", 150, deadline)
+        .unwrap();
+    proxy
+        .donate_to_campaign(&mut app, "donor", 0, &coins(100, "USDC"))
+        .unwrap_err();
+}
+
+#[test]
+pub fn instantiate_validation() {
+    let builder = || InstantiateMsg::builder(OWNER, DENOM, "distribution", "membership");
+
+    let err = builder().halftime(0).build().unwrap_err();
+    assert_eq!(err, ValidationError::ZeroHalftime);
+    let err = builder()
+        .direct_part(Decimal::percent(101))
+        .build()
+        .unwrap_err();
+    assert_eq!(err, ValidationError::InvalidDirectPart);
+    let err = InstantiateMsg::builder(OWNER, "1bc", "distribution", "membership")
+        .build()
+        .unwrap_err();
+    assert_eq!(err, ValidationError::InvalidDenom("1bc".to_owned()));
+
+    // contract checks the message on its own, built or not
+    let (mut app, _, _) = setup(&[]);
+    let mut msg = builder().build().unwrap();
+    msg.denom = "".to_owned();
+    let err = ProxyId::store_code(&mut app)
+        .instantiate_with(&mut app, OWNER, msg, "Proxy")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Validation(ValidationError::InvalidDenom(_))
+    ));
+}
+
+#[test]
+pub fn response_data() {
+    let (mut app, proxy, _) = setup(&["donor1", "donor2"]);
+
+    let data = proxy
+        .donate(&mut app, "donor1", &coins(100, DENOM))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        data,
+        DonateData {
+            direct: Uint128::new(15),
+            treasury: Uint128::zero(),
+            distributed: Uint128::new(85),
+            donations: 1,
+        }
+    );
+    let data = proxy
+        .donate(&mut app, "donor2", &coins(40, DENOM))
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.direct.u128(), 6);
+    assert_eq!(data.donations, 2);
+
+    // weight follows the donations counted since instantiation
+    let data = proxy
+        .withdraw(&mut app, OWNER, None, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        data,
+        WithdrawData {
+            amount: Uint128::new(balance(&app, &Addr::unchecked(OWNER))),
+            denom: DENOM.to_owned(),
+            receiver: None,
+            queued: None,
+            old_weight: 10,
+            new_weight: 2,
+        }
+    );
+
+    // reported even when halftime has not passed yet
+    let data = proxy.update_weight(&mut app, OWNER).unwrap().unwrap();
+    assert_eq!(
+        data,
+        UpdateWeightData {
+            performed: false,
+            old_weight: 2,
+            new_weight: 2,
+        }
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600 * 24 * 30));
+    let data = proxy.update_weight(&mut app, OWNER).unwrap().unwrap();
+    assert_eq!(
+        data,
+        UpdateWeightData {
+            performed: true,
+            old_weight: 2,
+            new_weight: 1,
+        }
+    );
+}
@@ -0,0 +1,43 @@
+// schema files of the contract, written by the proxy-schema binary and checked by tests/schema.rs
+// there is no distribution contract in this repository, so no distribution schema either
+use std::fs::{create_dir_all, write};
+use std::path::Path;
+
+use common::msg::{DonateData, ProposeMemberData, UpdateWeightData, WithdrawData};
+use cosmwasm_schema::{export_schema, generate_api, remove_schemas, schema_for};
+
+use crate::msg::{ExecMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+
+// full api and raw msg schemas in out_dir, response data schemas in out_dir/data
+pub fn write_schema(out_dir: &Path) {
+    create_dir_all(out_dir).unwrap();
+    remove_schemas(out_dir).unwrap();
+
+    let api = generate_api! {
+        instantiate: InstantiateMsg,
+        execute: ExecMsg,
+        query: QueryMsg,
+        migrate: MigrateMsg,
+    }
+    .render();
+    let json = api.to_string().unwrap();
+    write(
+        out_dir.join(format!("{}.json", env!("CARGO_PKG_NAME"))),
+        json + "\n",
+    )
+    .unwrap();
+
+    let raw_dir = out_dir.join("raw");
+    create_dir_all(&raw_dir).unwrap();
+    for (filename, json) in api.to_schema_files().unwrap() {
+        write(raw_dir.join(filename), json + "\n").unwrap();
+    }
+
+    // response data of a member proposal accepted by the group and of the proxy's own execs
+    let data_dir = out_dir.join("data");
+    create_dir_all(&data_dir).unwrap();
+    export_schema(&schema_for!(ProposeMemberData), &data_dir);
+    export_schema(&schema_for!(DonateData), &data_dir);
+    export_schema(&schema_for!(WithdrawData), &data_dir);
+    export_schema(&schema_for!(UpdateWeightData), &data_dir);
+}
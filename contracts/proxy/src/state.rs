@@ -1,6 +1,7 @@
+use common::msg::PauseState;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
@@ -9,18 +10,160 @@ pub struct Config {
     pub distribution_contract: Addr,
     pub membership_contract: Addr,
     pub is_closed: bool,
+    // slice of the distributed part going to the group treasury
+    pub treasury_part: Decimal,
 }
 
 #[cw_serde]
 pub struct WithdrawalData {
-    pub receiver: Addr,
+    // None => owner, or collaborators if the revenue split is set
+    pub receiver: Option<Addr>,
+    // of the first denom, there is only one then
     pub amount: Option<Uint128>,
+    pub denoms: Vec<String>,
+    // old and new weight of an explicit withdrawal, reported in its response data
+    pub weights: Option<(u64, u64)>,
+}
+
+#[cw_serde]
+pub struct SplitShare {
+    pub addr: Addr,
+    pub share: Decimal,
+}
+
+#[cw_serde]
+pub enum CampaignStatus {
+    Active,
+    Succeeded,
+    Failed,
+}
+
+#[cw_serde]
+pub struct Campaign {
+    pub title: String,
+    pub target: Uint128,
+    // seconds, same unit as LAST_UPDATED
+    pub deadline: u64,
+    pub raised: Uint128,
+    // number of donations pledged, added to DONATIONS on success
+    pub donations: u64,
+    pub status: CampaignStatus,
+}
+
+#[cw_serde]
+pub enum Permission {
+    Vote,
+    // cap limits what the operator withdraws in a withdrawal policy period, or during the whole
+    // grant without a period; operator has to pass the amount explicitly then
+    Withdraw { cap: Option<Uint128> },
+    Close,
+}
+
+#[cw_serde]
+pub struct Operator {
+    pub permissions: Vec<Permission>,
+    pub expiry: Option<u64>,
+}
+
+#[cw_serde]
+pub struct PendingOwner {
+    pub owner: Addr,
+    pub expiry: Option<u64>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct WithdrawalPolicy {
+    // None => any receiver
+    pub allowed_receivers: Option<Vec<Addr>>,
+    // withdrawals above it in a single period are timelocked
+    pub period_cap: Option<Uint128>,
+    pub period: u64,
+    pub timelock: u64,
+    // can cancel queued withdrawals next to the owner
+    pub guardian: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct PendingWithdrawal {
+    pub receiver: Option<Addr>,
+    pub amount: Uint128,
+    pub denom: String,
+    pub release_at: u64,
+}
+
+#[cw_serde]
+pub struct PeriodWithdrawals {
+    pub start: u64,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct Receipts {
+    pub contract: Addr,
+    pub threshold: Uint128,
+}
+
+#[cw_serde]
+pub struct FederatedGroup {
+    pub distribution: Addr,
+    // of the distributed part, the home group gets what is left
+    pub share: Decimal,
+}
+
+#[cw_serde]
+pub struct Swap {
+    pub router: Addr,
+    pub max_spread: Decimal,
+}
+
+#[cw_serde]
+pub struct PendingSwap {
+    pub donor: Addr,
+    pub offer: Coin,
+    // of the group denom before the swap, the difference is the donation
+    pub balance: Uint128,
+    pub minimum_receive: Option<Uint128>,
 }
 
 pub const OWNER: Item<Addr> = Item::new("owner");
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+pub const OPERATORS: Map<&Addr, Operator> = Map::new("operators");
+// withdrawn by capped operators, reset on every grant
+pub const OPERATOR_WITHDRAWALS: Map<&Addr, PeriodWithdrawals> = Map::new("operator_withdrawals");
+// shares sum up to 1
+pub const SPLIT: Item<Vec<SplitShare>> = Item::new("split");
+// when set, donations don't stay in the proxy but are forwarded to this address
+pub const AUTO_FORWARD: Item<Addr> = Item::new("auto_forward");
+// groups next to the home one from Config, by their membership contract
+pub const FEDERATED: Map<&Addr, FederatedGroup> = Map::new("federated");
+// membership contract => requested share, until its members accept this proxy
+pub const PENDING_GROUPS: Map<&Addr, Decimal> = Map::new("pending_groups");
+// withdrawal is paid out after the last distribution replied
+pub const AWAITING_WITHDRAWALS: Item<u64> = Item::new("awaiting_withdrawals");
+// ibc denom => origin chain, split and withdrawn the same way as the group denom
+pub const IBC_DENOMS: Map<&str, String> = Map::new("ibc_denoms");
+pub const SWAP: Item<Swap> = Item::new("swap");
+pub const PENDING_SWAP: Item<PendingSwap> = Item::new("pending_swap");
+pub const RECEIPTS: Item<Receipts> = Item::new("receipts");
+pub const NEXT_RECEIPT_ID: Item<u64> = Item::new("next_receipt_id");
+// pushed by membership
+pub const PAUSED: Item<PauseState> = Item::new("paused");
+// voted out, no weight in distribution
+pub const REMOVED: Item<bool> = Item::new("removed");
 pub const WEIGHT: Item<u64> = Item::new("weight");
 pub const DONATIONS: Item<u64> = Item::new("donations");
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const HALFTIME: Item<u64> = Item::new("halftime");
 pub const LAST_UPDATED: Item<u64> = Item::new("last_updated");
 pub const PENDING_WITHDRAWAL: Item<WithdrawalData> = Item::new("pending_withdrawal");
+pub const WITHDRAWAL_POLICY: Item<WithdrawalPolicy> = Item::new("withdrawal_policy");
+// new policy and the time it replaces the current one, so a leaked key can't lift limits at once
+pub const SCHEDULED_POLICY: Item<(WithdrawalPolicy, u64)> = Item::new("scheduled_policy");
+pub const PERIOD_WITHDRAWALS: Item<PeriodWithdrawals> = Item::new("period_withdrawals");
+pub const NEXT_WITHDRAWAL_ID: Item<u64> = Item::new("next_withdrawal_id");
+pub const QUEUED_WITHDRAWALS: Map<u64, PendingWithdrawal> = Map::new("queued_withdrawals");
+pub const NEXT_CAMPAIGN_ID: Item<u64> = Item::new("next_campaign_id");
+pub const CAMPAIGNS: Map<u64, Campaign> = Map::new("campaigns");
+// campaign id, donor => amount escrowed in this contract until the campaign is closed
+pub const PLEDGES: Map<(u64, &Addr), Uint128> = Map::new("pledges");
@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{read_dir, read_to_string, remove_dir_all};
+use std::path::{Path, PathBuf};
+
+// relative path => content
+fn files(dir: &Path) -> BTreeMap<PathBuf, String> {
+    let mut files = BTreeMap::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        for entry in read_dir(&current).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                let content = read_to_string(&path).unwrap();
+                files.insert(path.strip_prefix(dir).unwrap().to_path_buf(), content);
+            }
+        }
+    }
+    files
+}
+
+#[test]
+fn schema_is_current() {
+    // same output as the proxy-schema binary, written to a scratch dir
+    let out_dir = env::temp_dir().join(format!(
+        "{}-schema-{}",
+        env!("CARGO_PKG_NAME"),
+        std::process::id()
+    ));
+    proxy::schema::write_schema(&out_dir);
+    let generated = files(&out_dir);
+    remove_dir_all(&out_dir).unwrap();
+    let checked_in = files(&Path::new(env!("CARGO_MANIFEST_DIR")).join("schema"));
+
+    assert_eq!(
+        checked_in.keys().collect::<Vec<_>>(),
+        generated.keys().collect::<Vec<_>>()
+    );
+    for (path, content) in generated {
+        assert!(
+            checked_in[&path] == content,
+            "schema/{} is outdated, regenerate it with `cargo schema`",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use cosmwasm_std::{ensure, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cw721_base::state::TokenInfo;
+
+use crate::error::ContractError;
+use crate::msg::{ExecMsg, InstantiateMsg, IsMemberResponse, MembershipQueryMsg, QueryMsg};
+use crate::state::{Receipts, MEMBERSHIP};
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let membership = deps.api.addr_validate(&msg.membership_contract)?;
+    MEMBERSHIP.save(deps.storage, &membership)?;
+
+    let msg = cw721_base::InstantiateMsg {
+        name: msg.name,
+        symbol: msg.symbol,
+        minter: membership.into_string(),
+    };
+    let resp = Receipts::default().instantiate(deps, env, info, msg)?;
+    Ok(resp)
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecMsg,
+) -> Result<Response, ContractError> {
+    let receipts = Receipts::default();
+
+    match msg {
+        // base contract allows only one minter, receipts are minted by every member proxy
+        ExecMsg::Mint {
+            token_id,
+            owner,
+            token_uri,
+            extension,
+        } => {
+            ensure!(
+                is_member(deps.as_ref(), &info.sender)?,
+                ContractError::Unauthorized
+            );
+
+            let token = TokenInfo {
+                owner: deps.api.addr_validate(&owner)?,
+                approvals: vec![],
+                token_uri,
+                extension,
+            };
+            receipts
+                .tokens
+                .update(deps.storage, &token_id, |old| match old {
+                    Some(_) => Err(ContractError::AlreadyMinted),
+                    None => Ok(token),
+                })?;
+            receipts.increment_tokens(deps.storage)?;
+
+            let resp = Response::new()
+                .add_attribute("action", "mint")
+                .add_attribute("minter", info.sender.as_str())
+                .add_attribute("owner", owner)
+                .add_attribute("token_id", token_id);
+            Ok(resp)
+        }
+        msg => Ok(receipts.execute(deps, env, info, msg)?),
+    }
+}
+
+fn is_member(deps: Deps, addr: &Addr) -> StdResult<bool> {
+    let membership = MEMBERSHIP.load(deps.storage)?;
+    let resp: IsMemberResponse = deps.querier.query_wasm_smart(
+        membership,
+        &MembershipQueryMsg::IsMember {
+            addr: addr.to_string(),
+        },
+    )?;
+    Ok(resp.is_member)
+}
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    Receipts::default().query(deps, env, msg)
+}
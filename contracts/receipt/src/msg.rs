@@ -0,0 +1,31 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Empty, Uint128};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub membership_contract: String, // proxies of its members can mint
+}
+
+#[cw_serde]
+pub struct ReceiptMetadata {
+    pub creator: String, // owner of the proxy receiving the donation
+    pub amount: Uint128,
+    pub denom: String,
+    pub donated_at: u64,
+}
+
+pub type ExecMsg = cw721_base::ExecuteMsg<ReceiptMetadata, Empty>;
+pub type QueryMsg = cw721_base::QueryMsg<Empty>;
+
+// membership crate depends on this one, so its query is declared here
+#[cw_serde]
+pub enum MembershipQueryMsg {
+    IsMember { addr: String },
+}
+
+#[cw_serde]
+pub struct IsMemberResponse {
+    pub is_member: bool,
+}
@@ -0,0 +1,72 @@
+use anyhow::Result as AnyResult;
+use cosmwasm_std::Addr;
+use cw721::{NftInfoResponse, NumTokensResponse, TokensResponse};
+use cw_multi_test::{App, ContractWrapper};
+
+use crate::{
+    execute, instantiate,
+    msg::{QueryMsg, ReceiptMetadata},
+    query,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct CodeId(u64);
+
+impl CodeId {
+    pub fn store_code(app: &mut App) -> Self {
+        let contract = ContractWrapper::new(execute, instantiate, query);
+        CodeId(app.store_code(Box::new(contract)))
+    }
+}
+
+impl From<CodeId> for u64 {
+    fn from(value: CodeId) -> Self {
+        value.0
+    }
+}
+
+#[derive(Debug)]
+pub struct Contract(Addr);
+
+impl Contract {
+    pub fn addr(&self) -> &Addr {
+        &self.0
+    }
+
+    pub fn from_addr(addr: Addr) -> Self {
+        Self(addr)
+    }
+
+    pub fn tokens(&self, app: &App, owner: &str) -> AnyResult<Vec<String>> {
+        let resp: TokensResponse = app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Tokens {
+                owner: owner.to_owned(),
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(resp.tokens)
+    }
+
+    pub fn nft_info(
+        &self,
+        app: &App,
+        token_id: &str,
+    ) -> AnyResult<NftInfoResponse<ReceiptMetadata>> {
+        let resp = app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::NftInfo {
+                token_id: token_id.to_owned(),
+            },
+        )?;
+        Ok(resp)
+    }
+
+    pub fn num_tokens(&self, app: &App) -> AnyResult<u64> {
+        let resp: NumTokensResponse = app
+            .wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::NumTokens {})?;
+        Ok(resp.count)
+    }
+}
@@ -0,0 +1,9 @@
+use cosmwasm_std::{Addr, Empty};
+use cw721_base::Cw721Contract;
+use cw_storage_plus::Item;
+
+use crate::msg::ReceiptMetadata;
+
+pub type Receipts<'a> = Cw721Contract<'a, ReceiptMetadata, Empty, Empty, Empty>;
+
+pub const MEMBERSHIP: Item<Addr> = Item::new("membership");
@@ -0,0 +1,427 @@
+// the whole group wired up for multitest: membership, its proxies and the distribution stand-in
+// tests are short scenarios on top of it, see tests/scenarios.rs
+use std::collections::HashMap;
+
+use anyhow::Result as AnyResult;
+use common::msg::{
+    DistributionInstantiateMsg, DistributionQueryMsg, DonateData, PendingRewardsResponse,
+    ProposeMemberData, UpdateWeightData, WithdrawData,
+};
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Order, Response, StdError, StdResult, Uint128,
+};
+use cw4::{MemberListResponse, TotalWeightResponse};
+use cw_multi_test::{App, ContractWrapper};
+use cw_storage_plus::{Item, Map};
+use membership::msg::{
+    BadgeConfig, InstantiateMsg, QueryMsg as MembershipQueryMsg, ReceiptsConfig,
+};
+use membership::multitest::{CodeId as MembershipId, Contract as MembershipContract};
+use proxy::msg::DistribtionExecMsg;
+use proxy::multitest::{CodeId as ProxyId, Contract as ProxyContract};
+
+pub const DENOM: &str = "ORAI";
+pub const OWNER: &str = "owner"; // instantiates the group
+
+// splits every distribution between the members by their cw4 weight at that moment and pays
+// a proxy what it was given so far on withdraw, rounding dust stays in the contract
+pub fn store_distribution(app: &mut App) -> u64 {
+    const MEMBERSHIP: Item<Addr> = Item::new("membership");
+    const DENOM: Item<String> = Item::new("denom");
+    const OWED: Map<(&Addr, &str), Uint128> = Map::new("owed"); // (proxy, denom)
+
+    fn distribute(deps: DepsMut, funds: Vec<Coin>) -> StdResult<Response> {
+        let membership = MEMBERSHIP.load(deps.storage)?;
+        let total: TotalWeightResponse = deps.querier.query_wasm_smart(
+            &membership,
+            &MembershipQueryMsg::TotalWeight { at_height: None },
+        )?;
+        if total.weight == 0 {
+            return Ok(Response::new());
+        }
+
+        let mut start_after = None;
+        loop {
+            let page: MemberListResponse = deps.querier.query_wasm_smart(
+                &membership,
+                &MembershipQueryMsg::ListMembers {
+                    start_after: start_after.take(),
+                    limit: Some(30),
+                },
+            )?;
+            let Some(last) = page.members.last() else {
+                break;
+            };
+            start_after = Some(last.addr.clone());
+
+            for member in &page.members {
+                let proxy = membership::state::PROXIES
+                    .query(
+                        &deps.querier,
+                        membership.clone(),
+                        &Addr::unchecked(&member.addr),
+                    )?
+                    .ok_or_else(|| {
+                        StdError::generic_err(format!("{} has no proxy", member.addr))
+                    })?;
+                for coin in &funds {
+                    let share = coin.amount.multiply_ratio(member.weight, total.weight);
+                    OWED.update(deps.storage, (&proxy, &coin.denom), |owed| {
+                        StdResult::Ok(owed.unwrap_or_default() + share)
+                    })?;
+                }
+            }
+        }
+
+        Ok(Response::new())
+    }
+
+    fn withdraw(deps: DepsMut, proxy: Addr) -> StdResult<Response> {
+        let owed: Vec<_> = OWED
+            .prefix(&proxy)
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+        for (denom, _) in &owed {
+            OWED.remove(deps.storage, (&proxy, denom));
+        }
+
+        let amount: Vec<_> = owed
+            .into_iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(denom, amount)| Coin { denom, amount })
+            .collect();
+        if amount.is_empty() {
+            return Ok(Response::new());
+        }
+
+        Ok(Response::new().add_message(BankMsg::Send {
+            to_address: proxy.into_string(),
+            amount,
+        }))
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: DistribtionExecMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            DistribtionExecMsg::Distribute {} => distribute(deps, info.funds),
+            DistribtionExecMsg::Withdraw { .. } => withdraw(deps, info.sender),
+        }
+    }
+
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: DistributionInstantiateMsg,
+    ) -> StdResult<Response> {
+        MEMBERSHIP.save(deps.storage, &info.sender)?;
+        DENOM.save(deps.storage, &msg.denom)?;
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _env: Env, msg: DistributionQueryMsg) -> StdResult<Binary> {
+        match msg {
+            DistributionQueryMsg::PendingRewards { proxy } => {
+                let denom = DENOM.load(deps.storage)?;
+                let amount = OWED
+                    .may_load(deps.storage, (&Addr::unchecked(proxy), &denom))?
+                    .unwrap_or_default();
+                to_json_binary(&PendingRewardsResponse { amount })
+            }
+        }
+    }
+
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    app.store_code(Box::new(contract))
+}
+
+pub struct SystemBuilder {
+    denom: String,
+    starting_weight: u64,
+    direct_part: Decimal,
+    halftime: u64,
+    minimal_acceptance: u64,
+    members: Vec<String>,
+    guardian: Option<String>,
+    max_members: Option<u64>,
+    treasury_part: Option<Decimal>,
+    badges: Option<bool>,      // transferable
+    receipts: Option<Uint128>, // threshold
+    donors: Vec<(String, u128)>,
+}
+
+impl Default for SystemBuilder {
+    fn default() -> Self {
+        Self {
+            denom: DENOM.to_owned(),
+            starting_weight: 10,
+            direct_part: Decimal::percent(15),
+            halftime: 3600 * 24 * 30,
+            minimal_acceptance: 2,
+            members: vec!["member1".to_owned(), "member2".to_owned()],
+            guardian: None,
+            max_members: None,
+            treasury_part: None,
+            badges: None,
+            receipts: None,
+            donors: vec![],
+        }
+    }
+}
+
+impl SystemBuilder {
+    pub fn denom(mut self, denom: &str) -> Self {
+        self.denom = denom.to_owned();
+        self
+    }
+
+    pub fn starting_weight(mut self, weight: u64) -> Self {
+        self.starting_weight = weight;
+        self
+    }
+
+    pub fn direct_part(mut self, direct_part: Decimal) -> Self {
+        self.direct_part = direct_part;
+        self
+    }
+
+    pub fn halftime(mut self, halftime: u64) -> Self {
+        self.halftime = halftime;
+        self
+    }
+
+    pub fn minimal_acceptance(mut self, minimal_acceptance: u64) -> Self {
+        self.minimal_acceptance = minimal_acceptance;
+        self
+    }
+
+    pub fn members(mut self, members: &[&str]) -> Self {
+        self.members = members.iter().map(|member| member.to_string()).collect();
+        self
+    }
+
+    pub fn guardian(mut self, guardian: &str) -> Self {
+        self.guardian = Some(guardian.to_owned());
+        self
+    }
+
+    pub fn max_members(mut self, max_members: u64) -> Self {
+        self.max_members = Some(max_members);
+        self
+    }
+
+    pub fn treasury_part(mut self, treasury_part: Decimal) -> Self {
+        self.treasury_part = Some(treasury_part);
+        self
+    }
+
+    pub fn badges(mut self, transferable: bool) -> Self {
+        self.badges = Some(transferable);
+        self
+    }
+
+    pub fn receipts(mut self, threshold: u128) -> Self {
+        self.receipts = Some(Uint128::new(threshold));
+        self
+    }
+
+    // funded with the group denom at genesis
+    pub fn donor(mut self, addr: &str, amount: u128) -> Self {
+        self.donors.push((addr.to_owned(), amount));
+        self
+    }
+
+    #[track_caller]
+    pub fn build(self) -> System {
+        let mut app = App::new(|router, _api, storage| {
+            for (donor, amount) in &self.donors {
+                router
+                    .bank
+                    .init_balance(
+                        storage,
+                        &Addr::unchecked(donor),
+                        coins(*amount, &self.denom),
+                    )
+                    .unwrap();
+            }
+        });
+
+        let proxy_code_id = ProxyId::store_code(&mut app);
+        let distribution_code_id = store_distribution(&mut app);
+        let membership_code_id = MembershipId::store_code(&mut app);
+        let badge_code_id = badge::multitest::CodeId::store_code(&mut app);
+        let receipt_code_id = receipt::multitest::CodeId::store_code(&mut app);
+
+        let members: Vec<_> = self.members.iter().map(String::as_str).collect();
+        let mut msg =
+            InstantiateMsg::builder(&self.denom, proxy_code_id.into(), distribution_code_id)
+                .starting_weight(self.starting_weight)
+                .direct_part(self.direct_part)
+                .halftime(self.halftime)
+                .minimal_acceptance(self.minimal_acceptance)
+                .initial_members(&members);
+        if let Some(guardian) = &self.guardian {
+            msg = msg.guardian(guardian);
+        }
+        if let Some(max_members) = self.max_members {
+            msg = msg.max_members(max_members);
+        }
+        if let Some(treasury_part) = self.treasury_part {
+            msg = msg.treasury_part(treasury_part);
+        }
+        if let Some(transferable) = self.badges {
+            msg = msg.badge(BadgeConfig {
+                code_id: badge_code_id.into(),
+                transferable,
+            });
+        }
+        if let Some(threshold) = self.receipts {
+            msg = msg.receipts(ReceiptsConfig {
+                code_id: receipt_code_id.into(),
+                threshold,
+            });
+        }
+
+        let (membership, data) = membership_code_id
+            .instantiate_with(&mut app, OWNER, msg.build().unwrap(), "Membership")
+            .unwrap();
+
+        let distribution =
+            Addr::unchecked(membership.group_info(&app).unwrap().distribution_contract);
+        let proxies = data
+            .members
+            .into_iter()
+            .map(|member| {
+                let proxy = ProxyContract::from_addr(Addr::unchecked(member.proxy_addr));
+                (member.owner_addr, proxy)
+            })
+            .collect();
+
+        System {
+            app,
+            denom: self.denom,
+            membership,
+            distribution,
+            proxies,
+        }
+    }
+}
+
+pub struct System {
+    pub app: App,
+    pub denom: String,
+    pub membership: MembershipContract,
+    pub distribution: Addr,
+    proxies: HashMap<String, ProxyContract>, // by owner
+}
+
+impl System {
+    pub fn builder() -> SystemBuilder {
+        SystemBuilder::default()
+    }
+
+    #[track_caller]
+    pub fn proxy(&self, member: &str) -> &ProxyContract {
+        self.proxies
+            .get(member)
+            .unwrap_or_else(|| panic!("{member} is not a member"))
+    }
+
+    #[track_caller]
+    pub fn donate(
+        &mut self,
+        donor: &str,
+        member: &str,
+        amount: u128,
+    ) -> AnyResult<Option<DonateData>> {
+        let proxy = self.proxy(member).addr().clone();
+        ProxyContract::from_addr(proxy).donate(&mut self.app, donor, &coins(amount, &self.denom))
+    }
+
+    // member withdraws everything to themselves
+    #[track_caller]
+    pub fn withdraw(&mut self, member: &str) -> AnyResult<Option<WithdrawData>> {
+        let proxy = self.proxy(member).addr().clone();
+        ProxyContract::from_addr(proxy).withdraw(&mut self.app, member, None, None)
+    }
+
+    // candidate becomes a member on the last required acceptance, its proxy is tracked from then
+    #[track_caller]
+    pub fn propose(
+        &mut self,
+        member: &str,
+        candidate: &str,
+    ) -> AnyResult<Option<ProposeMemberData>> {
+        let proxy = self.proxy(member).addr().clone();
+        let data =
+            ProxyContract::from_addr(proxy).propose_member(&mut self.app, member, candidate)?;
+
+        if let Some(data) = &data {
+            let proxy = ProxyContract::from_addr(Addr::unchecked(&data.proxy_addr));
+            self.proxies.insert(data.owner_addr.clone(), proxy);
+        }
+
+        Ok(data)
+    }
+
+    // voter is a member, the proxy of the removed member stays tracked for its balance
+    #[track_caller]
+    pub fn propose_removal(&mut self, voter: &str, member: &str) -> AnyResult<()> {
+        let proxy = self.proxy(voter).addr().clone();
+        ProxyContract::from_addr(proxy).propose_removal(&mut self.app, voter, member, "scenario")
+    }
+
+    // decays the weight once halftime passed since the last update
+    #[track_caller]
+    pub fn update_weight(&mut self, member: &str) -> AnyResult<Option<UpdateWeightData>> {
+        let proxy = self.proxy(member).addr().clone();
+        ProxyContract::from_addr(proxy).update_weight(&mut self.app, member)
+    }
+
+    // every proxy ever created by owner, removed ones included
+    pub fn proxies(&self) -> &HashMap<String, ProxyContract> {
+        &self.proxies
+    }
+
+    pub fn advance_time(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += seconds / 5;
+        });
+    }
+
+    // what the distribution keeps for the member's proxy in the group denom
+    #[track_caller]
+    pub fn owed(&self, member: &str) -> u128 {
+        let msg = DistributionQueryMsg::PendingRewards {
+            proxy: self.proxy(member).addr().to_string(),
+        };
+        let rewards: PendingRewardsResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.distribution, &msg)
+            .unwrap();
+        rewards.amount.u128()
+    }
+
+    // in the group denom, addresses are either accounts or contracts
+    #[track_caller]
+    pub fn balances(&self, addrs: &[&str]) -> Vec<u128> {
+        addrs
+            .iter()
+            .map(|addr| {
+                self.app
+                    .wrap()
+                    .query_balance(*addr, &self.denom)
+                    .unwrap()
+                    .amount
+                    .u128()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,222 @@
+// random sequences of group activity, checking after every step that the weight and fund
+// bookkeeping of membership and proxies still add up
+use proptest::prelude::*;
+use test_support::System;
+
+const DONORS: [&str; 3] = ["donor0", "donor1", "donor2"];
+const DONOR_FUNDS: u128 = 1_000_000;
+const HALFTIME: u64 = 3600;
+
+#[derive(Clone, Debug)]
+enum Step {
+    Donate {
+        donor: usize,
+        member: usize,
+        amount: u128,
+    },
+    Withdraw {
+        member: usize,
+    },
+    Decay {
+        member: usize,
+        seconds: u64,
+    },
+    Join,
+    Leave {
+        member: usize,
+    },
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        4 => (0..DONORS.len(), any::<usize>(), 1..=1000u128)
+            .prop_map(|(donor, member, amount)| Step::Donate { donor, member, amount }),
+        2 => any::<usize>().prop_map(|member| Step::Withdraw { member }),
+        2 => (any::<usize>(), 0..=2 * HALFTIME)
+            .prop_map(|(member, seconds)| Step::Decay { member, seconds }),
+        1 => Just(Step::Join),
+        1 => any::<usize>().prop_map(|member| Step::Leave { member }),
+    ]
+}
+
+// owners currently in the group, by cw4 weight
+fn members(system: &System) -> Vec<(String, u64)> {
+    system
+        .membership
+        .list_members(&system.app, None, Some(30))
+        .unwrap()
+        .members
+        .into_iter()
+        .map(|member| (member.addr, member.weight))
+        .collect()
+}
+
+// funds that went through the distribution, the split itself is left to the distribution
+#[derive(Default)]
+struct Ledger {
+    distributed: u128,
+    paid: u128,
+}
+
+fn balance(system: &System, addr: &str) -> u128 {
+    system.balances(&[addr])[0]
+}
+
+fn check_invariants(system: &System, ledger: &Ledger) {
+    // no funds created or lost
+    let mut holders: Vec<String> = DONORS.iter().map(|donor| donor.to_string()).collect();
+    for (owner, proxy) in system.proxies() {
+        holders.push(owner.clone());
+        holders.push(proxy.addr().to_string());
+    }
+    holders.push(system.distribution.to_string());
+    holders.push(system.membership.addr().to_string());
+    let holders: Vec<_> = holders.iter().map(String::as_str).collect();
+    let total: u128 = system.balances(&holders).iter().sum();
+    assert_eq!(total, DONOR_FUNDS * DONORS.len() as u128);
+
+    // distribution never pays out more than it got, and what it owes is still there
+    assert!(ledger.paid <= ledger.distributed);
+    let kept = balance(system, system.distribution.as_str());
+    assert_eq!(kept, ledger.distributed - ledger.paid);
+    let owed: u128 = system
+        .proxies()
+        .keys()
+        .map(|owner| system.owed(owner))
+        .sum();
+    assert!(owed <= kept, "owes {owed}, keeps {kept}");
+
+    // group weights follow the proxies and sum up to the total weight
+    let members = members(system);
+    for (owner, weight) in &members {
+        let proxy = system.proxy(owner).addr().clone();
+        let proxy_weight = proxy::state::WEIGHT
+            .query(&system.app.wrap(), proxy)
+            .unwrap();
+        assert_eq!(proxy_weight, *weight, "weight of {owner}");
+        // weights go through i64 diffs, anything above would be a wrapped negative value
+        assert!(*weight <= i64::MAX as u64, "weight of {owner} wrapped");
+    }
+    let total_weight = system.membership.total_weight(&system.app).unwrap().weight;
+    assert_eq!(
+        total_weight,
+        members.iter().map(|(_, weight)| weight).sum::<u64>()
+    );
+}
+
+fn run(system: &mut System, ledger: &mut Ledger, step: Step, joined: &mut usize) {
+    let weights = members(system);
+    let members: Vec<_> = weights.iter().map(|(owner, _)| owner.clone()).collect();
+    let pick = |idx: usize| members[idx % members.len()].clone();
+    let distribution = system.distribution.to_string();
+
+    match step {
+        // every member gets at most the distributed amount together, zero weight gets nothing,
+        // and rounding keeps less than one unit per member
+        Step::Donate {
+            donor,
+            member,
+            amount,
+        } => {
+            let before = balance(system, &distribution);
+            let owed_before: Vec<_> = members.iter().map(|owner| system.owed(owner)).collect();
+            system.donate(DONORS[donor], &pick(member), amount).unwrap();
+            let distributed = balance(system, &distribution) - before;
+            ledger.distributed += distributed;
+
+            let mut shared = 0;
+            for ((owner, weight), owed_before) in weights.iter().zip(owed_before) {
+                let share = system
+                    .owed(owner)
+                    .checked_sub(owed_before)
+                    .unwrap_or_else(|| panic!("owed to {owner} went down"));
+                if *weight == 0 {
+                    assert_eq!(share, 0, "share of zero weight {owner}");
+                }
+                shared += share;
+            }
+            assert!(shared <= distributed, "shared {shared} of {distributed}");
+            if weights.iter().any(|(_, weight)| *weight > 0) {
+                let dust = distributed - shared;
+                assert!(dust < members.len() as u128, "dust {dust}");
+            }
+        }
+        // the owner gets the direct parts kept by the proxy and what the distribution owed
+        Step::Withdraw { member } => {
+            let member = pick(member);
+            let proxy = system.proxy(&member).addr().to_string();
+            let owed = system.owed(&member);
+            let [owner_before, proxy_before] = system.balances(&[&member, &proxy])[..] else {
+                unreachable!()
+            };
+            system.withdraw(&member).unwrap();
+            let payout = balance(system, &member) - owner_before - proxy_before;
+            assert_eq!(payout, owed, "payout of {member}");
+            assert_eq!(system.owed(&member), 0);
+            assert_eq!(balance(system, &proxy), 0);
+            ledger.paid += payout;
+        }
+        // decaying pulls the owed funds into the proxy before the weight changes
+        Step::Decay { member, seconds } => {
+            let member = pick(member);
+            let proxy = system.proxy(&member).addr().to_string();
+            let owed = system.owed(&member);
+            let before = balance(system, &proxy);
+            system.advance_time(seconds);
+            let data = system.update_weight(&member).unwrap().unwrap();
+            let payout = balance(system, &proxy) - before;
+            let expected = if data.performed { owed } else { 0 };
+            assert_eq!(payout, expected, "payout of {member}");
+            ledger.paid += payout;
+        }
+        Step::Join => {
+            let candidate = format!("candidate{joined}");
+            *joined += 1;
+            assert!(system.propose(&members[0], &candidate).unwrap().is_none());
+            assert!(system.propose(&members[1], &candidate).unwrap().is_some());
+        }
+        // two others vote the member out, enough members are kept to vote later on
+        Step::Leave { member } if members.len() > 3 => {
+            let member = member % members.len();
+            let voters: Vec<_> = (1..=2)
+                .map(|offset| members[(member + offset) % members.len()].clone())
+                .collect();
+            let member = &members[member];
+            let proxy = system.proxy(member).addr().to_string();
+            let owed = system.owed(member);
+            let before = balance(system, &proxy);
+            for voter in voters {
+                system.propose_removal(&voter, member).unwrap();
+            }
+
+            // the removed proxy gets what was owed to it
+            let payout = balance(system, &proxy) - before;
+            assert_eq!(payout, owed, "payout of {member}");
+            ledger.paid += payout;
+        }
+        Step::Leave { .. } => (),
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn bookkeeping_adds_up(steps in prop::collection::vec(step(), 1..30)) {
+        let mut builder = System::builder()
+            .members(&["member0", "member1", "member2"])
+            .halftime(HALFTIME);
+        for donor in DONORS {
+            builder = builder.donor(donor, DONOR_FUNDS);
+        }
+        let mut system = builder.build();
+        let mut ledger = Ledger::default();
+        check_invariants(&system, &ledger);
+
+        let mut joined = 0;
+        for step in steps {
+            run(&mut system, &mut ledger, step, &mut joined);
+            check_invariants(&system, &ledger);
+        }
+    }
+}
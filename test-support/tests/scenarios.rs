@@ -0,0 +1,83 @@
+use cosmwasm_std::coins;
+use test_support::{System, DENOM};
+
+#[test]
+fn adding_member() {
+    let mut system = System::builder().members(&["member1", "member2"]).build();
+
+    // both initial members have to accept
+    assert!(system.propose("member1", "candidate").unwrap().is_none());
+    let data = system.propose("member2", "candidate").unwrap().unwrap();
+    assert_eq!(data.owner_addr, "candidate");
+
+    let proxy = system.proxy("candidate").addr().to_string();
+    assert!(
+        system
+            .membership
+            .is_member(&system.app, &proxy)
+            .unwrap()
+            .is_member
+    );
+}
+
+#[test]
+fn donation_and_withdrawal() {
+    let mut system = System::builder().donor("donor", 100).build();
+
+    system.donate("donor", "member1", 100).unwrap();
+    let proxy = system.proxy("member1").addr().to_string();
+    let distribution = system.distribution.to_string();
+    assert_eq!(system.balances(&[&proxy, &distribution]), [15, 85]);
+
+    system.advance_time(3600 * 24);
+    system.withdraw("member1").unwrap();
+    // direct part and half of the distributed 85, both members weigh the same
+    assert_eq!(system.balances(&["member1", &proxy]), [15 + 42, 0]);
+
+    // nothing was donated to member2, it only gets its share
+    system.withdraw("member2").unwrap();
+    assert_eq!(system.balances(&["member2", &distribution]), [42, 1]);
+}
+
+#[test]
+fn group_overview() {
+    let mut system = System::builder().donor("donor", 150).build();
+    let start = system.app.block_info().time.seconds();
+
+    system.donate("donor", "member1", 100).unwrap();
+    system.donate("donor", "member2", 50).unwrap();
+    assert!(system.propose("member1", "candidate").unwrap().is_none());
+    system.propose_removal("member2", "member1").unwrap();
+
+    system.advance_time(3600 * 24 * 30);
+    system.update_weight("member2").unwrap();
+    let decayed_at = system.app.block_info().time.seconds();
+
+    let stats = system.membership.group_stats(&system.app).unwrap();
+    assert_eq!(stats.member_count, 2);
+    assert_eq!(stats.total_weight, 15);
+    assert_eq!(stats.total_donated, coins(150, DENOM));
+    // decaying member2 paid out its 42 + 21 of the distributed 128
+    assert_eq!(stats.distribution_balance, coins(65, DENOM));
+    assert_eq!(stats.pending_additions, 1);
+    assert_eq!(stats.pending_removals, 1);
+    assert_eq!(stats.open_spend_proposals, 0);
+    assert_eq!(stats.last_decay, Some(decayed_at));
+
+    let details = system
+        .membership
+        .member_details(&system.app, "member1")
+        .unwrap();
+    assert_eq!(details.proxy, system.proxy("member1").addr().as_str());
+    assert_eq!(details.weight, 10);
+    assert_eq!(details.donations, Some(1));
+    assert_eq!(details.last_updated, Some(start));
+    assert!(!details.is_closed);
+    assert_eq!(details.pending_rewards.u128(), 42 + 21);
+
+    // only members have details
+    system
+        .membership
+        .member_details(&system.app, "candidate")
+        .unwrap_err();
+}